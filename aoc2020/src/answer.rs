@@ -0,0 +1,154 @@
+use crate::grid::Grid;
+use std::fmt;
+
+/// The answer to a single part of a puzzle
+///
+/// Answers are compared by value rather than by variant, so an
+/// `Unsigned(5)` is equal to a `Signed(5)`, and a number is equal to the
+/// `Text` of its digits, as that's how they're recorded in the answers file.
+/// Anything else is compared by how it's displayed
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(u128),
+    Text(String),
+    /// A picture drawn in characters, e.g. letters made of `#` and `.`
+    Grid(Grid<char>),
+}
+
+impl Answer {
+    /// Parses an answer as it'd be written in the answers file, taking the
+    /// narrowest variant which fits
+    ///
+    /// Anything with more than one line of the same width is a `Grid`
+    pub fn parse(answer: &str) -> Self {
+        let trimmed = answer.trim();
+        if let Ok(n) = trimmed.parse::<u64>() {
+            return Answer::Unsigned(n);
+        }
+        if let Ok(n) = trimmed.parse::<i64>() {
+            return Answer::Signed(n);
+        }
+        if let Ok(n) = trimmed.parse::<u128>() {
+            return Answer::Big(n);
+        }
+        let rows: Vec<Vec<char>> = trimmed.lines().map(|l| l.chars().collect()).collect();
+        if rows.len() > 1 && rows.iter().all(|row| row.len() == rows[0].len()) {
+            return Answer::Grid(Grid::from_rows(rows));
+        }
+        Answer::Text(answer.to_string())
+    }
+
+    pub fn is_multi_line(&self) -> bool {
+        match self {
+            Answer::Grid(grid) => grid.height() > 1,
+            Answer::Text(text) => text.trim().contains('\n'),
+            _ => false,
+        }
+    }
+
+    /// `(is_negative, magnitude)` if the answer is a number
+    fn as_number(&self) -> Option<(bool, u128)> {
+        match self {
+            Answer::Signed(n) => Some((*n < 0, n.unsigned_abs() as u128)),
+            Answer::Unsigned(n) => Some((false, *n as u128)),
+            Answer::Big(n) => Some((false, *n)),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.to_string().trim() == other.to_string().trim(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(grid) => write!(f, "{}", grid),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident($target:ty): $($from:ty),+) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(n: $from) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(Signed(i64): i8, i16, i32, i64, isize);
+impl_from!(Unsigned(u64): u8, u16, u32, u64, usize);
+impl_from!(Big(u128): u128);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+impl From<Grid<char>> for Answer {
+    fn from(grid: Grid<char>) -> Self {
+        Answer::Grid(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_are_equal_across_variants() {
+        assert_eq!(Answer::from(5u32), Answer::from(5i64));
+        assert_eq!(Answer::from(5usize), Answer::Big(5));
+        assert_ne!(Answer::from(-5), Answer::from(5u8));
+        assert_eq!(Answer::from(241861950u64), Answer::from("241861950"));
+    }
+
+    #[test]
+    fn parses_the_narrowest_variant() {
+        assert!(matches!(Answer::parse("42"), Answer::Unsigned(42)));
+        assert!(matches!(Answer::parse("-42"), Answer::Signed(-42)));
+        let big = u128::from(u64::MAX) + 1;
+        assert!(matches!(Answer::parse(&big.to_string()), Answer::Big(n) if n == big));
+        assert!(matches!(
+            Answer::parse("mxmxvkd,sqjhc,fvjkl"),
+            Answer::Text(_)
+        ));
+        assert!(matches!(Answer::parse("#.\n.#\n"), Answer::Grid(_)));
+        assert!(matches!(Answer::parse("#.\n.\n"), Answer::Text(_)));
+    }
+
+    #[test]
+    fn grids_are_compared_by_how_they_look() {
+        let grid = Grid::from_rows(vec![vec!['#', '.'], vec!['.', '#']]);
+        let answer = Answer::from(grid);
+        assert!(answer.is_multi_line());
+        assert_eq!(answer.to_string(), "#.\n.#");
+        assert_eq!(answer, Answer::from("#.\n.#\n"));
+        assert_ne!(answer, Answer::from("#.\n##"));
+    }
+}
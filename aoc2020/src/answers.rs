@@ -0,0 +1,400 @@
+use crate::{
+    input::NamedInputs,
+    parse::ParseError,
+    solution::{Part, Solver},
+    Answer,
+};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Answers are stored as `{dir}/{year}.toml`
+pub const DEFAULT_ANSWERS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers");
+
+pub fn answers_path(year: u16) -> PathBuf {
+    Path::new(DEFAULT_ANSWERS_DIR).join(format!("{}.toml", year))
+}
+
+/// The accepted answers for a year, recorded as
+///
+/// ```toml
+/// [day1]
+/// part1 = 514579
+/// part2 = "241861950"
+/// ```
+///
+/// Answers can be written as either numbers or strings, numbers too big for
+/// TOML have to be strings. Pictures are written as multi-line strings, which
+/// are read as an [`Answer::Grid`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), Answer>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayAnswers {
+    part1: Option<RecordedAnswer>,
+    part2: Option<RecordedAnswer>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RecordedAnswer {
+    Number(i64),
+    Text(String),
+}
+
+impl RecordedAnswer {
+    fn into_answer(self) -> Answer {
+        match self {
+            RecordedAnswer::Number(n) => n.into(),
+            RecordedAnswer::Text(s) => Answer::parse(&s),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers for `year` from [`DEFAULT_ANSWERS_DIR`]
+    pub fn load(year: u16) -> Result<Self, AnswersError> {
+        Self::load_from(answers_path(year))
+    }
+
+    /// Loads the answers which go with a set of named inputs, there are none
+    /// if they haven't been recorded yet
+    pub fn load_named(inputs: &NamedInputs) -> Result<Self, AnswersError> {
+        let path = inputs.answers_path();
+        if path.exists() {
+            Self::load_from(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, AnswersError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents).map_err(|message| AnswersError::Invalid {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let days: BTreeMap<String, DayAnswers> =
+            toml::from_str(contents).map_err(|e| e.to_string())?;
+        let mut answers = BTreeMap::new();
+        for (key, day_answers) in days {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| format!("expected a table named `day<number>`, found `{}`", key))?;
+            let parts = vec![
+                (Part::One, day_answers.part1),
+                (Part::Two, day_answers.part2),
+            ];
+            for (part, answer) in parts {
+                if let Some(answer) = answer {
+                    answers.insert((day, part), answer.into_answer());
+                }
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+/// Returned when an answers file can't be read or is malformed
+#[derive(Debug)]
+pub enum AnswersError {
+    Io { path: PathBuf, source: io::Error },
+    Invalid { path: PathBuf, message: String },
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "failed to read answers `{}`: {}", path.display(), source)
+            }
+            AnswersError::Invalid { path, message } => {
+                write!(f, "invalid answers `{}`: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl Error for AnswersError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Invalid { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Answer,
+    },
+    /// There's no recorded answer, so the part wasn't run
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Runs every part of `solution` which has a recorded answer against `input`
+pub fn verify(
+    solution: &dyn Solver,
+    input: &str,
+    answers: &Answers,
+) -> Result<Vec<(Part, Verdict)>, ParseError> {
+    let expected: Vec<_> = Part::ALL
+        .iter()
+        .map(|part| (*part, answers.get(solution.day(), *part)))
+        .collect();
+    if expected.iter().all(|(_, answer)| answer.is_none()) {
+        return Ok(Part::ALL
+            .iter()
+            .map(|part| (*part, Verdict::Missing))
+            .collect());
+    }
+
+    // Answers are always for the puzzle as set, so never for an overridden config
+    let parsed = solution.parse(input)?;
+    let config = solution.default_config();
+    Ok(expected
+        .into_iter()
+        .map(|(part, expected)| {
+            let verdict = match expected {
+                None => Verdict::Missing,
+                Some(expected) => {
+                    let actual = solution.solve(part, parsed.as_ref(), config.as_ref());
+                    if actual == *expected {
+                        Verdict::Pass
+                    } else {
+                        Verdict::Fail {
+                            expected: expected.clone(),
+                            actual,
+                        }
+                    }
+                }
+            };
+            (part, verdict)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_numbers_and_strings() {
+        let answers = Answers::parse("[day1]\npart1 = 514579\npart2 = \"241861950\"\n").unwrap();
+        assert_eq!(answers.get(1, Part::One), Some(&Answer::from(514579)));
+        assert_eq!(answers.get(1, Part::Two), Some(&Answer::from(241861950)));
+        assert_eq!(answers.get(2, Part::One), None);
+        assert!(Answers::parse("[one]\npart1 = 1\n").is_err());
+        assert!(Answers::parse("[day1]\npart3 = 1\n").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "day1")]
+    fn reports_pass_fail_and_missing() {
+        use crate::{find_solution, YEAR};
+
+        let solution = find_solution(YEAR, 1).unwrap();
+        let input = "1721\n979\n366\n299\n675\n1456";
+        let answers = Answers::parse("[day1]\npart1 = 514579\npart2 = 1\n").unwrap();
+        assert_eq!(
+            verify(solution, input, &answers).unwrap(),
+            vec![
+                (Part::One, Verdict::Pass),
+                (
+                    Part::Two,
+                    Verdict::Fail {
+                        expected: Answer::from(1),
+                        actual: Answer::from(241861950)
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            verify(solution, input, &Answers::default()).unwrap(),
+            vec![(Part::One, Verdict::Missing), (Part::Two, Verdict::Missing)]
+        );
+    }
+
+    /// Checks every recorded answer for `day` against its real input, at
+    /// least one part should have an answer recorded
+    #[cfg(feature = "all")]
+    fn assert_verified(day: u8) {
+        use crate::{find_solution, input::load_input, YEAR};
+
+        let answers = Answers::load(YEAR).unwrap();
+        let solution = find_solution(YEAR, day).unwrap();
+        let input = load_input(YEAR, day).unwrap();
+        let verdicts = verify(solution, &input, &answers).unwrap();
+        for (part, verdict) in &verdicts {
+            if *verdict != Verdict::Missing {
+                assert_eq!(*verdict, Verdict::Pass, "day {} part {}", day, part);
+            }
+        }
+        assert!(
+            verdicts.iter().any(|(_, v)| *v == Verdict::Pass),
+            "day {} has no recorded answers",
+            day
+        );
+    }
+
+    /// [`verify`] only runs each day's main implementation, so every
+    /// alternative is checked against the recorded answers here
+    #[test]
+    #[cfg(feature = "all")]
+    fn every_alternative_gives_the_recorded_answer() {
+        use crate::{input::load_input, solution::MAIN, SOLUTIONS, YEAR};
+
+        let answers = Answers::load(YEAR).unwrap();
+        for solution in SOLUTIONS {
+            let day = solution.day();
+            let alternatives: Vec<(Part, &str)> = Part::ALL
+                .iter()
+                .flat_map(|part| {
+                    solution
+                        .implementations(*part)
+                        .into_iter()
+                        .filter(|name| *name != MAIN)
+                        .map(move |name| (*part, name))
+                })
+                .collect();
+            if alternatives.is_empty() {
+                continue;
+            }
+            let input = solution.parse(&load_input(YEAR, day).unwrap()).unwrap();
+            let config = solution.default_config();
+            for (part, name) in alternatives {
+                let expected = answers
+                    .get(day, part)
+                    .unwrap_or_else(|| panic!("day {} part {} has no recorded answer", day, part));
+                assert_eq!(
+                    solution.solve_with(part, name, input.as_ref(), config.as_ref()),
+                    Some(expected.clone()),
+                    "day {} part {} ({})",
+                    day,
+                    part,
+                    name
+                );
+            }
+        }
+    }
+
+    /// Anyone else's inputs checked in under `input/<year>/<name>` have to
+    /// give the answers recorded alongside them
+    #[test]
+    #[cfg(feature = "all")]
+    fn every_named_input_is_verified() {
+        use crate::{
+            find_solution,
+            input::{named_inputs, DEFAULT_INPUT_DIR},
+            YEAR,
+        };
+
+        for inputs in named_inputs(DEFAULT_INPUT_DIR, YEAR).unwrap() {
+            let answers = Answers::load_named(&inputs).unwrap();
+            for day in (1..=25).filter(|day| inputs.has(*day)) {
+                let solution = match find_solution(YEAR, day) {
+                    Some(solution) => solution,
+                    None => continue,
+                };
+                let input = inputs.load(day).unwrap();
+                for (part, verdict) in verify(solution, &input, &answers).unwrap() {
+                    if verdict != Verdict::Missing {
+                        assert_eq!(
+                            verdict,
+                            Verdict::Pass,
+                            "{} day {} part {}",
+                            inputs.name,
+                            day,
+                            part
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "all")]
+    macro_rules! verify_days {
+        ($($(#[$attr:meta])* $name:ident: $day:expr,)*) => {
+            $(
+                #[test]
+                $(#[$attr])*
+                fn $name() {
+                    assert_verified($day);
+                }
+            )*
+
+            /// Days which don't have any answers yet, such as one which was
+            /// just added with `aoc new`, can't be checked
+            #[test]
+            fn every_solution_is_verified() {
+                let answers = Answers::load(crate::YEAR).unwrap();
+                let answered: Vec<u8> = crate::SOLUTIONS
+                    .iter()
+                    .map(|s| s.day())
+                    .filter(|day| Part::ALL.iter().any(|part| answers.get(*day, *part).is_some()))
+                    .collect();
+                assert_eq!(answered, vec![$($day),*]);
+            }
+        };
+    }
+
+    // Every day is checked, so they all need to be there
+    #[cfg(feature = "all")]
+    verify_days! {
+        verify_day_1: 1,
+        verify_day_2: 2,
+        verify_day_3: 3,
+        verify_day_4: 4,
+        verify_day_5: 5,
+        verify_day_6: 6,
+        verify_day_7: 7,
+        verify_day_8: 8,
+        verify_day_9: 9,
+        verify_day_10: 10,
+        verify_day_11: 11,
+        verify_day_12: 12,
+        verify_day_13: 13,
+        verify_day_14: 14,
+        verify_day_15: 15,
+        verify_day_16: 16,
+        verify_day_17: 17,
+        verify_day_18: 18,
+        verify_day_19: 19,
+        verify_day_20: 20,
+    }
+}
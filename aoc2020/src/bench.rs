@@ -0,0 +1,341 @@
+use crate::{
+    parse::ParseError,
+    solution::{Part, Solver},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Untimed runs made before measuring, to warm up caches and allocations
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            iterations: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Generator,
+    Part1,
+    Part2,
+}
+
+impl From<Part> for Phase {
+    fn from(part: Part) -> Self {
+        match part {
+            Part::One => Phase::Part1,
+            Part::Two => Phase::Part2,
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Generator => "generator",
+            Phase::Part1 => "part 1",
+            Phase::Part2 => "part 2",
+        })
+    }
+}
+
+/// Summary of the timings of a single phase of a day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    #[serde(rename = "min_ns", with = "nanos")]
+    pub min: Duration,
+    #[serde(rename = "median_ns", with = "nanos")]
+    pub median: Duration,
+    #[serde(rename = "p95_ns", with = "nanos")]
+    pub p95: Duration,
+}
+
+impl Measurement {
+    /// ## Panics
+    ///
+    /// If there are no `samples`
+    pub fn from_samples(day: u8, phase: Phase, mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "at least one sample is required");
+        samples.sort();
+        let len = samples.len();
+        let median = if len.is_multiple_of(2) {
+            (samples[len / 2 - 1] + samples[len / 2]) / 2
+        } else {
+            samples[len / 2]
+        };
+        // Nearest rank, so the p95 of fewer than 20 samples is the maximum
+        let p95 = samples[(len * 95).div_ceil(100) - 1];
+        Self {
+            day,
+            phase,
+            min: samples[0],
+            median,
+            p95,
+        }
+    }
+}
+
+/// Every measurement from a benchmark run, this is what's written out as JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub year: u16,
+    pub warmup: usize,
+    pub iterations: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+impl Report {
+    pub fn new(year: u16, options: BenchOptions) -> Self {
+        Self {
+            year,
+            warmup: options.warmup,
+            iterations: options.iterations,
+            measurements: Vec::new(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("report should always serialize")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<&Measurement> {
+        self.measurements
+            .iter()
+            .find(|m| m.day == day && m.phase == phase)
+    }
+
+    /// Compares the median of every measurement with the same measurement in
+    /// `baseline`, measurements which aren't in both reports are skipped
+    pub fn compare(&self, baseline: &Report) -> Vec<Comparison> {
+        self.measurements
+            .iter()
+            .filter_map(|current| {
+                baseline
+                    .get(current.day, current.phase)
+                    .map(|baseline| Comparison {
+                        day: current.day,
+                        phase: current.phase,
+                        baseline: baseline.median,
+                        current: current.median,
+                    })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Comparison {
+    pub day: u8,
+    pub phase: Phase,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Comparison {
+    /// Percentage change from the baseline, positive if it's got slower
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.as_secs_f64();
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - baseline) / baseline * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BenchError {
+    /// Nothing can be measured without at least one iteration
+    NoIterations,
+    Parse(ParseError),
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchError::NoIterations => write!(f, "the number of iterations has to be at least 1"),
+            BenchError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for BenchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BenchError::NoIterations => None,
+            BenchError::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for BenchError {
+    fn from(e: ParseError) -> Self {
+        BenchError::Parse(e)
+    }
+}
+
+/// Times the generator and each part of `solution` separately, each phase is
+/// run `options.warmup` times untimed before being measured
+pub fn bench(
+    solution: &dyn Solver,
+    input: &str,
+    options: BenchOptions,
+) -> Result<Vec<Measurement>, BenchError> {
+    if options.iterations == 0 {
+        return Err(BenchError::NoIterations);
+    }
+    let day = solution.day();
+    let parsed = solution.parse(input)?;
+    let config = solution.default_config();
+    let mut measurements = vec![Measurement::from_samples(
+        day,
+        Phase::Generator,
+        samples(options, || {
+            solution
+                .parse(input)
+                .expect("input has already been parsed successfully");
+        }),
+    )];
+    for part in Part::ALL.iter() {
+        measurements.push(Measurement::from_samples(
+            day,
+            (*part).into(),
+            samples(options, || {
+                solution.solve(*part, parsed.as_ref(), config.as_ref());
+            }),
+        ));
+    }
+    Ok(measurements)
+}
+
+fn samples(options: BenchOptions, mut f: impl FnMut()) -> Vec<Duration> {
+    for _ in 0..options.warmup {
+        f();
+    }
+    (0..options.iterations)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .collect()
+}
+
+pub(crate) mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::{convert::TryFrom, time::Duration};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::YEAR;
+
+    fn millis(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().map(|ms| Duration::from_millis(*ms)).collect()
+    }
+
+    #[test]
+    fn summarises_samples() {
+        let m = Measurement::from_samples(1, Phase::Part1, millis(&[5, 1, 3, 2, 4]));
+        assert_eq!(m.min, Duration::from_millis(1));
+        assert_eq!(m.median, Duration::from_millis(3));
+        assert_eq!(m.p95, Duration::from_millis(5));
+
+        let samples: Vec<u64> = (1..=40).rev().collect();
+        let m = Measurement::from_samples(1, Phase::Part1, millis(&samples));
+        assert_eq!(m.median, Duration::from_micros(20_500));
+        assert_eq!(m.p95, Duration::from_millis(38));
+    }
+
+    #[test]
+    fn report_round_trips_through_json() {
+        let mut report = Report::new(YEAR, BenchOptions::default());
+        report.measurements.push(Measurement::from_samples(
+            3,
+            Phase::Generator,
+            millis(&[1, 2]),
+        ));
+        let json = report.to_json();
+        assert!(json.contains("\"phase\": \"generator\""));
+        assert!(json.contains("\"median_ns\": 1500000"));
+        assert_eq!(Report::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn compares_medians_with_baseline() {
+        let options = BenchOptions::default();
+        let mut baseline = Report::new(YEAR, options);
+        baseline
+            .measurements
+            .push(Measurement::from_samples(1, Phase::Part1, millis(&[10])));
+        let mut current = Report::new(YEAR, options);
+        current.measurements.extend(vec![
+            Measurement::from_samples(1, Phase::Part1, millis(&[12])),
+            Measurement::from_samples(1, Phase::Part2, millis(&[12])),
+        ]);
+
+        let comparisons = current.compare(&baseline);
+        assert_eq!(comparisons.len(), 1);
+        assert!((comparisons[0].change() - 20.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(25.0));
+    }
+
+    #[test]
+    #[cfg(feature = "day1")]
+    fn benches_every_phase() {
+        use crate::find_solution;
+
+        let solution = find_solution(YEAR, 1).unwrap();
+        let options = BenchOptions {
+            warmup: 0,
+            iterations: 3,
+        };
+        let measurements = bench(solution, "1721\n979\n366\n299\n675\n1456", options).unwrap();
+        let phases: Vec<_> = measurements.iter().map(|m| m.phase).collect();
+        assert_eq!(phases, vec![Phase::Generator, Phase::Part1, Phase::Part2]);
+        assert!(measurements
+            .iter()
+            .all(|m| m.min <= m.median && m.median <= m.p95));
+
+        let options = BenchOptions {
+            warmup: 0,
+            iterations: 0,
+        };
+        assert_eq!(
+            bench(solution, "1721", options),
+            Err(BenchError::NoIterations)
+        );
+    }
+}
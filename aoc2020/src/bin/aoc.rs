@@ -0,0 +1,755 @@
+//! Standalone runner for the 2020 solutions which doesn't depend on
+//! `cargo-aoc` being installed.
+//!
+//! ```sh
+//! cargo run --release --bin aoc -- list
+//! cargo run --release --bin aoc -- run 8
+//! cargo run --release --bin aoc -- run 8 --part 2 --input path/to/input.txt
+//! cargo run --release --bin aoc -- run 7 --set "target=dark olive"
+//! cat input.txt | cargo run --release --bin aoc -- run 8 --input -
+//! cargo run --release --bin aoc -- run-all
+//! cargo run --release --bin aoc -- run-all --sequential
+//! cargo run --release --bin aoc -- run-all --every-input
+//! cargo run --release --bin aoc -- bench 15 --iterations 20 --json bench.json
+//! cargo run --release --bin aoc -- gen 8 --size 10000 --seed 1 > day8.txt
+//! AOC_SESSION=... cargo run --release --bin aoc -- submit 8 2
+//! cargo run --release --bin aoc -- report --readme readme.md
+//! cargo run --release --bin aoc -- new 21
+//! cargo run --release --bin aoc -- picture --output day20.ppm
+//! ```
+use aoc2020::{
+    answers::{self, Answers, Verdict},
+    bench::{self, BenchOptions, Report},
+    client::Client,
+    config::Overrides,
+    find_solution,
+    gen::{self, Rng},
+    input::{self, NamedInputs},
+    memory::{self, Allocations},
+    metrics::{self, Metrics},
+    report::{self, Progress},
+    runner::{self, Status},
+    scaffold,
+    solution::{Part, Solver, MAIN},
+    Answer, SOLUTIONS, YEAR,
+};
+use std::{
+    any::Any,
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
+
+const USAGE: &str = "Usage:
+    aoc list
+    aoc run <day> [--part <1|2>] [--input <path|-> | --input-dir <dir>]
+                  [--name <name>] [--config <path>] [--set <key>=<value>]...
+    aoc run-all [--input-dir <dir>] [--config <path>] [--answers <path>]
+                [--threads <n> | --sequential] [--metrics <path>]
+                [--name <name> | --every-input]
+    aoc verify [<day>] [--input-dir <dir>] [--answers <path>]
+    aoc bench [<day>] [--input-dir <dir>] [--iterations <n>] [--warmup <n>]
+              [--json <path|->] [--baseline <path>] [--threshold <percent>]
+    aoc gen <day> [--size <n>] [--seed <n>]
+    aoc fetch [<day>] [--input-dir <dir>]
+    aoc submit <day> <1|2> [--input-dir <dir>]
+    aoc report [--input-dir <dir>] [--answers <path>] [--bench <path>]
+               [--readme <path>]
+    aoc new <day> [--input-dir <dir>]
+    aoc picture [--input <path|-> | --input-dir <dir>] [--format <text|pgm|ppm>]
+                [--scale <n>] [--output <path>]
+
+Inputs are read from `<dir>/<year>/day<day>.txt`, where `<dir>` defaults to
+$AOC_INPUT_DIR, or the crate's `input` directory if that isn't set
+
+`run-all` runs every part at once on a thread per core, `--sequential` runs
+them one at a time so their timings aren't affected by each other
+
+`--name` reads inputs from `<dir>/<year>/<name>/day<day>.txt` instead, with
+`run-all` checking them against `<dir>/<year>/<name>/answers.toml` unless
+`--answers` is given. `--every-input` runs every solution against each of
+those sets in turn, and flags any set where a part fails or panics
+
+`fetch` downloads any missing inputs, and `submit` solves a part with its
+input and submits the answer. Both need $AOC_SESSION set to your session
+cookie, and talk to $AOC_URL if it's set rather than adventofcode.com
+
+`--config` overrides each day's puzzle parameters from a TOML file with a
+`[day<day>]` table per day, `--set` overrides a single parameter of the day
+being run and takes precedence over the file
+
+`report` lists which parts are implemented, unfinished or missing and which
+give their recorded answer, with their best time from the `bench --json`
+report given with `--bench`, or from running them once otherwise. With
+`--readme` the table replaces the progress section of that file
+
+`new` adds a day to the crate: its module, with both parts left as `todo!()`,
+its feature, its entry in the list of solutions and an empty input
+
+`picture` draws how day 20's tiles fit together, with a line between each
+tile and its id above it, and any sea monsters picked out. As text, or as a
+PGM or PPM image with each cell `--scale` pixels square, 4 by default. The
+format is taken from the `--output` file's extension if it isn't given
+
+Built with `--features alloc-stats`, `run` also reports how many allocations
+the generator and each part made, how many bytes they asked for in total and
+the most they had live at once, and `run-all` reports the same for each part. With `--features metrics` they
+show the counters and spans each part recorded, which `run-all --metrics`
+writes out as JSON";
+
+struct Run {
+    generator: Duration,
+    generator_allocations: Option<Allocations>,
+    /// Every implementation of each part, with the main one first
+    parts: Vec<PartRun>,
+}
+
+struct PartRun {
+    part: Part,
+    name: &'static str,
+    answer: Answer,
+    elapsed: Duration,
+    allocations: Option<Allocations>,
+    metrics: Option<Metrics>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {}\n\n{}", e, USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let (command, args) = args.split_first().ok_or("no command given")?;
+    match command.as_str() {
+        "list" => {
+            for solution in SOLUTIONS {
+                println!("{} day {}", solution.year(), solution.day());
+            }
+            Ok(())
+        }
+        "run" => {
+            let (day, args) = args.split_first().ok_or("no day given")?;
+            let solution = find_day(day)?;
+            let parts = match flag(args, &["-p", "--part"])? {
+                Some("1") => vec![Part::One],
+                Some("2") => vec![Part::Two],
+                Some(p) => return Err(format!("invalid part `{}`", p)),
+                None => Part::ALL.to_vec(),
+            };
+            let input = match (flag(args, &["-i", "--input"])?, flag(args, &["--name"])?) {
+                (Some(path), _) => input::read_input(path),
+                (None, Some(name)) => {
+                    NamedInputs::new(input_dir(args)?, YEAR, name).load(solution.day())
+                }
+                (None, None) => {
+                    input::load_input_from(input_dir(args)?, solution.year(), solution.day())
+                }
+            }
+            .map_err(|e| e.to_string())?;
+            let mut overrides = overrides(args)?;
+            for assignment in flags(args, "--set")? {
+                overrides.set(solution.day(), assignment)?;
+            }
+            let config = overrides.config_for(solution)?;
+            print_run(
+                solution,
+                &run_solution(solution, &input, config.as_ref(), &parts)?,
+            );
+            Ok(())
+        }
+        "run-all" => {
+            let threads = if args.iter().any(|a| a == "--sequential") {
+                1
+            } else {
+                match parsed_flag(args, "--threads")? {
+                    Some(threads) => threads,
+                    None => thread::available_parallelism().map_or(1, |n| n.get()),
+                }
+            };
+            let overrides = overrides(args)?;
+            if args.iter().any(|a| a == "--every-input") {
+                return run_every_input(&input_dir(args)?, &overrides, threads);
+            }
+
+            let start = Instant::now();
+            let outcomes = match flag(args, &["--name"])? {
+                Some(name) => {
+                    let inputs = NamedInputs::new(input_dir(args)?, YEAR, name);
+                    if !inputs.dir.is_dir() {
+                        return Err(format!("`{}` doesn't exist", inputs.dir.display()));
+                    }
+                    let answers = match flag(args, &["--answers"])? {
+                        Some(_) => answers(args)?,
+                        None => Answers::load_named(&inputs).map_err(|e| e.to_string())?,
+                    };
+                    runner::run_named(SOLUTIONS, &inputs, &overrides, &answers, threads)
+                }
+                None => runner::run_all(
+                    SOLUTIONS,
+                    &input_dir(args)?,
+                    &overrides,
+                    &answers(args)?,
+                    threads,
+                ),
+            };
+            let elapsed = start.elapsed();
+            print!("{}", runner::table(&outcomes));
+            if let Some(path) = flag(args, &["--metrics"])? {
+                fs::write(path, runner::metrics_json(&outcomes))
+                    .map_err(|e| format!("failed to write `{}`: {}", path, e))?;
+            }
+
+            let tally = Tally::new(&outcomes);
+            println!("\n{} in {:?} on {} thread(s)", tally, elapsed, threads);
+            if tally.failed > 0 {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        "verify" => {
+            let solutions = selected_days(args)?;
+            verify(&solutions, &input_dir(args)?, &answers(args)?)
+        }
+        "bench" => {
+            let defaults = BenchOptions::default();
+            let options = BenchOptions {
+                warmup: parsed_flag(args, "--warmup")?.unwrap_or(defaults.warmup),
+                iterations: parsed_flag(args, "--iterations")?.unwrap_or(defaults.iterations),
+            };
+            let baseline = match flag(args, &["--baseline"])? {
+                Some(path) => {
+                    let json = fs::read_to_string(path)
+                        .map_err(|e| format!("failed to read `{}`: {}", path, e))?;
+                    let report = Report::from_json(&json)
+                        .map_err(|e| format!("invalid baseline `{}`: {}", path, e))?;
+                    Some(report)
+                }
+                None => None,
+            };
+            let threshold = parsed_flag(args, "--threshold")?.unwrap_or(10.0);
+            let json = flag(args, &["--json"])?;
+            // Keep stdout clean for the report if it's being written there
+            let mut out: Box<dyn Write> = match json {
+                Some("-") => Box::new(io::stderr()),
+                _ => Box::new(io::stdout()),
+            };
+
+            let dir = input_dir(args)?;
+            let mut report = Report::new(YEAR, options);
+            for solution in selected_days(args)? {
+                let input = input::load_input_from(&dir, solution.year(), solution.day())
+                    .map_err(|e| e.to_string())?;
+                let measurements =
+                    bench::bench(solution, &input, options).map_err(|e| e.to_string())?;
+                for m in &measurements {
+                    writeln!(
+                        out,
+                        "Day {:>2} - {:<9} : min {:>12?}  median {:>12?}  p95 {:>12?}",
+                        m.day, m.phase, m.min, m.median, m.p95
+                    )
+                    .map_err(|e| e.to_string())?;
+                }
+                report.measurements.extend(measurements);
+            }
+
+            match json {
+                Some("-") => println!("{}", report.to_json()),
+                Some(path) => fs::write(path, report.to_json())
+                    .map_err(|e| format!("failed to write `{}`: {}", path, e))?,
+                None => {}
+            }
+            if let Some(baseline) = baseline {
+                compare(&mut out, &report, &baseline, threshold).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        }
+        "gen" => {
+            let (day, args) = args.split_first().ok_or("no day given")?;
+            let day: u8 = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+            let generator =
+                gen::find_generator(day).ok_or_else(|| format!("day {} has no generator", day))?;
+            let size = parsed_flag(args, "--size")?.unwrap_or(generator.default_size);
+            let seed = match parsed_flag(args, "--seed")? {
+                Some(seed) => seed,
+                None => {
+                    // So the input can be generated again
+                    let seed = Rng::from_time().next_u64();
+                    eprintln!("seed: {}", seed);
+                    seed
+                }
+            };
+            println!("{}", (generator.generate)(&mut Rng::new(seed), size));
+            Ok(())
+        }
+        "fetch" => {
+            let client = client(args)?;
+            for solution in selected_days(args)? {
+                client
+                    .input(solution.year(), solution.day())
+                    .map_err(|e| e.to_string())?;
+                println!(
+                    "Day {} : {}",
+                    solution.day(),
+                    input::input_path(input_dir(args)?, solution.year(), solution.day()).display()
+                );
+            }
+            Ok(())
+        }
+        "submit" => {
+            let (day, args) = args.split_first().ok_or("no day given")?;
+            let solution = find_day(day)?;
+            let part = match args.first().map(String::as_str) {
+                Some("1") => Part::One,
+                Some("2") => Part::Two,
+                Some(p) => return Err(format!("invalid part `{}`", p)),
+                None => return Err("no part given".to_string()),
+            };
+            let client = client(args)?;
+            let input = client
+                .input(solution.year(), solution.day())
+                .map_err(|e| e.to_string())?;
+            let answer = solution.run(part, &input).map_err(|e| e.to_string())?;
+            let submission = client
+                .submit(solution.year(), solution.day(), part, &answer)
+                .map_err(|e| e.to_string())?;
+            println!(
+                "Day {} - Part {} : {} is {}",
+                solution.day(),
+                part,
+                shown(&answer),
+                submission
+            );
+            Ok(())
+        }
+        "report" => {
+            let dir = input_dir(args)?;
+            let sources = report::scan(Path::new(report::SOURCE_DIR))
+                .map_err(|e| format!("failed to read the solutions: {}", e))?;
+            let bench = match flag(args, &["--bench"])? {
+                Some(path) => {
+                    let json = fs::read_to_string(path)
+                        .map_err(|e| format!("failed to read `{}`: {}", path, e))?;
+                    Some(Report::from_json(&json).map_err(|e| e.to_string())?)
+                }
+                None => None,
+            };
+            // One at a time so the timings are comparable
+            let answers = answers(args)?;
+            let outcomes = runner::run_all(SOLUTIONS, &dir, &overrides(args)?, &answers, 1);
+            let table = Progress::new(YEAR, &sources, &answers, &outcomes, bench.as_ref(), &dir)
+                .to_markdown();
+            match flag(args, &["--readme"])? {
+                Some(path) => {
+                    let readme = fs::read_to_string(path)
+                        .map_err(|e| format!("failed to read `{}`: {}", path, e))?;
+                    fs::write(path, report::update_readme(&readme, &table)?)
+                        .map_err(|e| format!("failed to write `{}`: {}", path, e))
+                }
+                None => {
+                    print!("{}", table);
+                    Ok(())
+                }
+            }
+        }
+        "new" => {
+            let day = args.first().ok_or("no day given")?;
+            let day: u8 = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+            let changed =
+                scaffold::scaffold(Path::new(scaffold::CRATE_DIR), &input_dir(args)?, YEAR, day)?;
+            for path in changed {
+                println!("{}", path.display());
+            }
+            println!(
+                "\nAdd a generator for it to `src/gen`, and its answers to `answers/{}.toml` \
+                 once they're known",
+                YEAR
+            );
+            Ok(())
+        }
+        #[cfg(feature = "day20")]
+        "picture" => picture(args),
+        "help" | "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        other => Err(format!("unknown command `{}`", other)),
+    }
+}
+
+/// Draws how day 20's tiles fit together, as text or as an image
+#[cfg(feature = "day20")]
+fn picture(args: &[String]) -> Result<(), String> {
+    use aoc2020::day_20::{generator, Jigsaw};
+
+    let input = match flag(args, &["-i", "--input"])? {
+        Some(path) => input::read_input(path),
+        None => input::load_input_from(input_dir(args)?, YEAR, 20),
+    }
+    .map_err(|e| e.to_string())?;
+    let photos = generator(&input).map_err(|e| e.to_string())?;
+    let assembly = Jigsaw::new(&photos).assemble().map_err(|e| e.to_string())?;
+
+    let output = flag(args, &["-o", "--output"])?;
+    let format = match flag(args, &["--format"])? {
+        Some(format) => format,
+        None => output
+            .and_then(|path| Path::new(path).extension())
+            .and_then(|extension| extension.to_str())
+            .unwrap_or("text"),
+    };
+    let scale = parsed_flag(args, "--scale")?.unwrap_or(4);
+    if scale == 0 {
+        return Err("the scale has to be at least 1".to_string());
+    }
+    let picture = match format {
+        "text" | "txt" => assembly.to_text(&photos).into_bytes(),
+        "pgm" => assembly.to_pgm(&photos, scale),
+        "ppm" => assembly.to_ppm(&photos, scale),
+        other => {
+            return Err(format!(
+                "unknown format `{}`, expected `text`, `pgm` or `ppm`",
+                other
+            ))
+        }
+    };
+    match output {
+        Some(path) => {
+            fs::write(path, picture).map_err(|e| format!("failed to write `{}`: {}", path, e))
+        }
+        None => io::stdout().write_all(&picture).map_err(|e| e.to_string()),
+    }
+}
+
+/// How many parts passed, failed or couldn't be checked
+struct Tally {
+    passed: usize,
+    failed: usize,
+    unchecked: usize,
+}
+
+impl Tally {
+    fn new(outcomes: &[runner::Outcome]) -> Self {
+        let count = |f: fn(&Status) -> bool| outcomes.iter().filter(|o| f(&o.status)).count();
+        Self {
+            passed: count(|s| *s == Status::Pass),
+            failed: count(|s| matches!(s, Status::Fail { .. } | Status::Error(_))),
+            unchecked: count(|s| *s == Status::Unchecked),
+        }
+    }
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} unchecked",
+            self.passed, self.failed, self.unchecked
+        )
+    }
+}
+
+/// Runs every solution against each set of named inputs in `dir`, checking
+/// them against that set's own answers, then lists the sets which had a part
+/// fail or panic
+fn run_every_input(dir: &Path, overrides: &Overrides, threads: usize) -> Result<(), String> {
+    let named = input::named_inputs(dir, YEAR).map_err(|e| e.to_string())?;
+    if named.is_empty() {
+        return Err(format!(
+            "there are no named inputs in `{}`",
+            dir.join(YEAR.to_string()).display()
+        ));
+    }
+
+    let mut flagged = Vec::new();
+    for inputs in &named {
+        let answers = Answers::load_named(inputs).map_err(|e| e.to_string())?;
+        let outcomes = runner::run_named(SOLUTIONS, inputs, overrides, &answers, threads);
+        let tally = Tally::new(&outcomes);
+        println!("== {} ==\n", inputs.name);
+        print!("{}", runner::table(&outcomes));
+        println!("\n{}\n", tally);
+        if tally.failed > 0 {
+            let parts: Vec<String> = outcomes
+                .iter()
+                .filter(|o| matches!(o.status, Status::Fail { .. } | Status::Error(_)))
+                .map(|o| format!("day {} part {}", o.day, o.part))
+                .collect();
+            flagged.push(format!("{}: {}", inputs.name, parts.join(", ")));
+        }
+    }
+
+    if flagged.is_empty() {
+        println!("All {} input(s) gave their recorded answers", named.len());
+        return Ok(());
+    }
+    println!("{} of {} input(s) flagged", flagged.len(), named.len());
+    for line in flagged {
+        println!("    {}", line);
+    }
+    process::exit(1);
+}
+
+fn find_day(day: &str) -> Result<&'static dyn Solver, String> {
+    let day: u8 = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+    find_solution(YEAR, day).ok_or_else(|| format!("day {} has no solution", day))
+}
+
+/// The day given as the first argument, or every day if there isn't one
+fn selected_days(args: &[String]) -> Result<Vec<&'static dyn Solver>, String> {
+    match args.first().filter(|a| !a.starts_with('-')) {
+        Some(day) => Ok(vec![find_day(day)?]),
+        None => Ok(SOLUTIONS.to_vec()),
+    }
+}
+
+/// Runs the generator once, then every implementation of each of the `parts`
+/// against its output
+fn run_solution(
+    solution: &dyn Solver,
+    input: &str,
+    config: &dyn Any,
+    parts: &[Part],
+) -> Result<Run, String> {
+    let start = Instant::now();
+    let (parsed, generator_allocations) = memory::measure(|| solution.parse(input));
+    let generator = start.elapsed();
+    let parsed = parsed.map_err(|e| e.to_string())?;
+    let parts = parts
+        .iter()
+        .flat_map(|part| {
+            solution
+                .implementations(*part)
+                .into_iter()
+                .map(move |name| (*part, name))
+        })
+        .map(|(part, name)| {
+            let start = Instant::now();
+            let ((answer, allocations), metrics) = metrics::measure(|| {
+                memory::measure(|| {
+                    solution
+                        .solve_with(part, name, parsed.as_ref(), config)
+                        .expect("implementation should exist")
+                })
+            });
+            PartRun {
+                part,
+                name,
+                answer,
+                elapsed: start.elapsed(),
+                allocations,
+                metrics,
+            }
+        })
+        .collect();
+    Ok(Run {
+        generator,
+        generator_allocations,
+        parts,
+    })
+}
+
+/// Checks each solution against the recorded `answers`, failing if any of
+/// them don't match or couldn't be run
+fn verify(solutions: &[&dyn Solver], dir: &Path, answers: &Answers) -> Result<(), String> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solution in solutions {
+        let verdicts = input::load_input_from(dir, solution.year(), solution.day())
+            .map_err(|e| e.to_string())
+            .and_then(|input| {
+                answers::verify(*solution, &input, answers).map_err(|e| e.to_string())
+            });
+        match verdicts {
+            Ok(verdicts) => {
+                for (part, verdict) in verdicts {
+                    println!("Day {} - Part {} : {}", solution.day(), part, verdict);
+                    match verdict {
+                        Verdict::Pass => passed += 1,
+                        Verdict::Fail { .. } => failed += 1,
+                        Verdict::Missing => missing += 1,
+                    }
+                }
+            }
+            Err(e) => {
+                println!("Day {} : error, {}", solution.day(), e);
+                failed += 1;
+            }
+        }
+    }
+    println!(
+        "\n{} passed, {} failed, {} missing",
+        passed, failed, missing
+    );
+    if failed > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Prints how each median has changed since the `baseline`, exiting with an
+/// error if any of them have slowed down by more than `threshold` percent
+fn compare(
+    out: &mut dyn Write,
+    report: &Report,
+    baseline: &Report,
+    threshold: f64,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "\nCompared to baseline (regression threshold {}%)",
+        threshold
+    )?;
+    let mut regressions = 0;
+    for c in report.compare(baseline) {
+        let marker = if c.is_regression(threshold) {
+            regressions += 1;
+            "  REGRESSION"
+        } else {
+            ""
+        };
+        writeln!(
+            out,
+            "Day {:>2} - {:<9} : {:>12?} -> {:>12?} ({:+.1}%){}",
+            c.day,
+            c.phase,
+            c.baseline,
+            c.current,
+            c.change(),
+            marker
+        )?;
+    }
+    if regressions > 0 {
+        writeln!(out, "\n{} regression(s) found", regressions)?;
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Finds the value following any of the `names` given, ie. `--part 2`
+fn flag<'a>(args: &'a [String], names: &[&str]) -> Result<Option<&'a str>, String> {
+    match args.iter().position(|a| names.contains(&a.as_str())) {
+        Some(i) => args
+            .get(i + 1)
+            .map(|v| Some(v.as_str()))
+            .ok_or_else(|| format!("`{}` requires a value", args[i])),
+        None => Ok(None),
+    }
+}
+
+/// Every value following `name`, for flags which can be given more than once
+fn flags<'a>(args: &'a [String], name: &str) -> Result<Vec<&'a str>, String> {
+    args.iter()
+        .enumerate()
+        .filter(|(_, a)| *a == name)
+        .map(|(i, _)| {
+            args.get(i + 1)
+                .map(String::as_str)
+                .ok_or_else(|| format!("`{}` requires a value", name))
+        })
+        .collect()
+}
+
+fn parsed_flag<T: FromStr>(args: &[String], name: &str) -> Result<Option<T>, String> {
+    flag(args, &[name])?
+        .map(|v| {
+            v.parse()
+                .map_err(|_| format!("invalid value `{}` for `{}`", v, name))
+        })
+        .transpose()
+}
+
+/// The `--input-dir` flag if it was given, otherwise [`input::input_dir`]
+fn input_dir(args: &[String]) -> Result<PathBuf, String> {
+    Ok(flag(args, &["--input-dir"])?
+        .map(PathBuf::from)
+        .unwrap_or_else(input::input_dir))
+}
+
+/// A client configured from the environment, caching inputs in the
+/// `--input-dir`
+fn client(args: &[String]) -> Result<Client, String> {
+    Ok(Client::from_env()
+        .map_err(|e| e.to_string())?
+        .with_input_dir(input_dir(args)?))
+}
+
+/// The answers from the `--answers` file, or the recorded answers for the year
+fn answers(args: &[String]) -> Result<Answers, String> {
+    match flag(args, &["--answers"])? {
+        Some(path) => Answers::load_from(path),
+        None => Answers::load(YEAR),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// The overrides from the `--config` file, or none if it wasn't given
+fn overrides(args: &[String]) -> Result<Overrides, String> {
+    match flag(args, &["--config"])? {
+        Some(path) => Overrides::load_from(path).map_err(|e| e.to_string()),
+        None => Ok(Overrides::default()),
+    }
+}
+
+fn print_run(solution: &dyn Solver, run: &Run) {
+    for PartRun {
+        part,
+        name,
+        answer,
+        elapsed,
+        allocations,
+        metrics,
+    } in &run.parts
+    {
+        if *name == MAIN {
+            println!("Day {} - Part {} : {}", solution.day(), part, shown(answer));
+        } else {
+            // The main implementation is always run first
+            let expected = &run
+                .parts
+                .iter()
+                .find(|p| p.part == *part && p.name == MAIN)
+                .expect("main implementation should have been run")
+                .answer;
+            let verdict = if answer == expected {
+                String::new()
+            } else {
+                format!(" MISMATCH (main gave {})", shown(expected))
+            };
+            println!(
+                "Day {} - Part {} ({}) : {}{}",
+                solution.day(),
+                part,
+                name,
+                shown(answer),
+                verdict
+            );
+        }
+        println!("\tgenerator: {:?},", run.generator);
+        if let Some(allocations) = run.generator_allocations {
+            println!("\tgenerator allocations: {},", allocations);
+        }
+        println!("\trunner: {:?}", elapsed);
+        if let Some(allocations) = allocations {
+            println!("\trunner allocations: {}", allocations);
+        }
+        if let Some(metrics) = metrics.as_ref().filter(|m| !m.is_empty()) {
+            println!("\tmetrics: {}", metrics);
+        }
+        println!();
+    }
+}
+
+/// Multi-line answers start on a line of their own so they line up
+fn shown(answer: &Answer) -> String {
+    if answer.is_multi_line() {
+        format!("\n{}\n", answer)
+    } else {
+        answer.to_string()
+    }
+}
@@ -0,0 +1,275 @@
+//! Just enough HTTP to talk to Advent of Code, without pulling in an HTTP
+//! client and TLS stack for two endpoints
+
+use std::{
+    io::{self, Read, Write},
+    net::TcpStream,
+    process::{Command, Stdio},
+    time::Duration,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// Always sent as `application/x-www-form-urlencoded`
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// Sends a request and waits for the whole response
+pub trait Transport: Send + Sync {
+    fn send(&self, request: &Request) -> io::Result<Response>;
+}
+
+/// Plain HTTP/1.1 over a `TcpStream`, this can't do `https` so it's only
+/// really useful against the [`MockServer`]
+///
+/// [`MockServer`]: super::mock::MockServer
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TcpTransport;
+
+impl Transport for TcpTransport {
+    fn send(&self, request: &Request) -> io::Result<Response> {
+        let rest = request.url.strip_prefix("http://").ok_or_else(|| {
+            invalid_data(format!(
+                "only `http://` urls are supported: {}",
+                request.url
+            ))
+        })?;
+        let (host, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+
+        let mut stream = TcpStream::connect(host)?;
+        stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+            request.method.as_str(),
+            path,
+            host
+        );
+        for (name, value) in &request.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        let body = request.body.as_deref().unwrap_or("");
+        if request.body.is_some() {
+            head.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        }
+        head.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(body.as_bytes())?;
+
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw)?;
+        parse_response(&raw)
+    }
+}
+
+/// Shells out to `curl`, which is what's used for the real site as it
+/// handles `https`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurlTransport;
+
+/// Appended to curl's output so the status can be split off the body
+const STATUS_MARKER: &str = "\n--aoc-status:";
+
+impl CurlTransport {
+    /// The headers are read from stdin rather than given as arguments, as
+    /// anyone on the machine can see a process's arguments, and one of them
+    /// is the session cookie
+    fn command(request: &Request) -> Command {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--request"])
+            .arg(request.method.as_str())
+            .arg("--write-out")
+            .arg(format!("{}%{{http_code}}", STATUS_MARKER));
+        if !request.headers.is_empty() {
+            command.args(["--header", "@-"]);
+        }
+        if let Some(body) = &request.body {
+            command.arg("--data-raw").arg(body);
+        }
+        command.arg(&request.url);
+        command
+    }
+
+    /// The headers as curl reads them from a file, one per line
+    fn headers(request: &Request) -> io::Result<String> {
+        let mut headers = String::new();
+        for (name, value) in &request.headers {
+            if name.contains(['\r', '\n']) || value.contains(['\r', '\n']) {
+                return Err(invalid_data(format!("invalid header `{}`", name)));
+            }
+            headers.push_str(&format!("{}: {}\n", name, value));
+        }
+        Ok(headers)
+    }
+}
+
+impl Transport for CurlTransport {
+    fn send(&self, request: &Request) -> io::Result<Response> {
+        let headers = Self::headers(request)?;
+        let mut child = Self::command(request)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // Dropped once it's written, so curl sees the end of the headers
+            stdin.write_all(headers.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let stdout = String::from_utf8(output.stdout).map_err(|e| invalid_data(e.to_string()))?;
+        let (body, status) = stdout
+            .rsplit_once(STATUS_MARKER)
+            .ok_or_else(|| invalid_data("curl didn't write the status".to_string()))?;
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid status `{}`", status)))?;
+        Ok(Response {
+            status,
+            body: body.to_string(),
+        })
+    }
+}
+
+/// Parses a whole HTTP/1.1 response, the body is taken as everything after
+/// the headers as the connection is always closed after a single request
+pub(crate) fn parse_response(raw: &str) -> io::Result<Response> {
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| invalid_data("response ended before the headers did".to_string()))?;
+    let status_line = head.lines().next().unwrap_or_default();
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(|| invalid_data(format!("invalid status line `{}`", status_line)))?;
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+/// Encodes a single value of an `application/x-www-form-urlencoded` body
+pub fn form_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b' ' => "+".to_string(),
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Decodes a single value of an `application/x-www-form-urlencoded` body,
+/// invalid escapes are left as they are
+pub fn form_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_status_and_body() {
+        let response =
+            parse_response("HTTP/1.1 404 Not Found\r\nContent-Length: 3\r\n\r\n404").unwrap();
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body, "404");
+        assert!(parse_response("HTTP/1.1 200 OK\r\n").is_err());
+    }
+
+    #[test]
+    fn curl_reads_headers_from_stdin() {
+        let request = Request {
+            method: Method::Get,
+            url: "https://adventofcode.com/2020/day/1/input".to_string(),
+            headers: vec![("Cookie".to_string(), "session=secret".to_string())],
+            body: None,
+        };
+        let command = CurlTransport::command(&request);
+        let args: Vec<_> = command.get_args().map(|a| a.to_string_lossy()).collect();
+        assert!(args.iter().all(|a| !a.contains("secret")), "{:?}", args);
+        assert!(args.windows(2).any(|w| w[0] == "--header" && w[1] == "@-"));
+        assert_eq!(
+            CurlTransport::headers(&request).unwrap(),
+            "Cookie: session=secret\n"
+        );
+
+        let mut injected = request;
+        injected.headers[0].1.push_str("\nX-Other: 1");
+        assert!(CurlTransport::headers(&injected).is_err());
+    }
+
+    #[test]
+    fn form_values_round_trip() {
+        let value = "mxmxvkd,sqjhc fvjkl&=%";
+        let encoded = form_encode(value);
+        assert_eq!(encoded, "mxmxvkd%2Csqjhc+fvjkl%26%3D%25");
+        assert_eq!(form_decode(&encoded), value);
+    }
+}
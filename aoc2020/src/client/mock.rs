@@ -0,0 +1,276 @@
+use super::http::{self, Method};
+use crate::solution::Part;
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+/// A request as the [`MockServer`] received it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Received {
+    pub method: Method,
+    pub path: String,
+    /// The `session` cookie, if one was sent
+    pub session: Option<String>,
+    pub body: String,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    inputs: HashMap<(u16, u8), String>,
+    answers: HashMap<(u16, u8, Part), String>,
+    received: Vec<Received>,
+}
+
+/// An in-process stand in for adventofcode.com, serving inputs and judging
+/// answers in the same shape as the real site
+///
+/// It listens on a random local port until it's dropped
+pub struct MockServer {
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    /// How many of the next submissions are told to wait
+    rate_limited: Arc<AtomicUsize>,
+    stopped: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts a server which only accepts requests with the given `session`
+    pub fn start(session: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+        let rate_limited = Arc::new(AtomicUsize::new(0));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let (state, rate_limited, stopped) = (
+                Arc::clone(&state),
+                Arc::clone(&rate_limited),
+                Arc::clone(&stopped),
+            );
+            let session = session.to_string();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stopped.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    // A client which hangs up early only affects its own request
+                    if let Ok(stream) = stream {
+                        let _ = handle(stream, &session, &state, &rate_limited);
+                    }
+                }
+            })
+        };
+        Ok(Self {
+            address,
+            state,
+            rate_limited,
+            stopped,
+            handle: Some(handle),
+        })
+    }
+
+    /// The base url to give a client, i.e. `http://127.0.0.1:1234`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    pub fn set_input(&self, year: u16, day: u8, input: &str) {
+        self.lock().inputs.insert((year, day), input.to_string());
+    }
+
+    pub fn set_answer(&self, year: u16, day: u8, part: Part, answer: &str) {
+        self.lock()
+            .answers
+            .insert((year, day, part), answer.to_string());
+    }
+
+    /// Tells the next `submissions` answers to wait before submitting again
+    pub fn rate_limit(&self, submissions: usize) {
+        self.rate_limited.store(submissions, Ordering::SeqCst);
+    }
+
+    /// Every request received so far, in order
+    pub fn received(&self) -> Vec<Received> {
+        self.lock().received.clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .expect("the server never panics while locked")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wakes the listener up so it sees it's been stopped
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    session: &str,
+    state: &Mutex<State>,
+    rate_limited: &AtomicUsize,
+) -> io::Result<()> {
+    let received = read_request(&mut stream)?;
+    let mut state = state.lock().expect("the server never panics while locked");
+    state.received.push(received.clone());
+
+    let (status, body) = if received.session.as_deref() != Some(session) {
+        (
+            400,
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".to_string(),
+        )
+    } else {
+        route(&received, &state, rate_limited)
+    };
+    drop(state);
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn route(received: &Received, state: &State, rate_limited: &AtomicUsize) -> (u16, String) {
+    let segments: Vec<&str> = received.path.trim_matches('/').split('/').collect();
+    let (year, day, endpoint) = match segments.as_slice() {
+        [year, "day", day, endpoint] => match (year.parse::<u16>(), day.parse::<u8>()) {
+            (Ok(year), Ok(day)) => (year, day, *endpoint),
+            _ => return not_found(),
+        },
+        _ => return not_found(),
+    };
+
+    match (received.method, endpoint) {
+        (Method::Get, "input") => match state.inputs.get(&(year, day)) {
+            Some(input) => (200, input.clone()),
+            None => not_found(),
+        },
+        (Method::Post, "answer") => {
+            let form: HashMap<&str, String> = received
+                .body
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .map(|(key, value)| (key, http::form_decode(value)))
+                .collect();
+            let part = match form.get("level").map(String::as_str) {
+                Some("1") => Part::One,
+                Some("2") => Part::Two,
+                _ => return (400, "Missing level".to_string()),
+            };
+            let answer = form.get("answer").map_or("", String::as_str);
+
+            let waiting = rate_limited
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            let message = if waiting {
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 42s left to wait."
+                    .to_string()
+            } else {
+                judge(state.answers.get(&(year, day, part)), answer)
+            };
+            (
+                200,
+                format!("<main><article><p>{}</p></article></main>", message),
+            )
+        }
+        _ => not_found(),
+    }
+}
+
+fn judge(expected: Option<&String>, answer: &str) -> String {
+    let expected = match expected {
+        Some(expected) => expected,
+        None => {
+            return "You don't seem to be solving the right level.  Did you already complete it?"
+                .to_string()
+        }
+    };
+    if answer == expected {
+        return "That's the right answer!  You are one gold star closer to saving your vacation."
+            .to_string();
+    }
+    let hint = match (answer.parse::<i128>(), expected.parse::<i128>()) {
+        (Ok(answer), Ok(expected)) if answer > expected => "; your answer is too high",
+        (Ok(answer), Ok(expected)) if answer < expected => "; your answer is too low",
+        _ => "",
+    };
+    format!(
+        "That's not the right answer{}.  If you're stuck, make sure you're using the full input \
+         data.  Please wait one minute before trying again.",
+        hint
+    )
+}
+
+fn not_found() -> (u16, String) {
+    (404, "404 Not Found".to_string())
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<Received> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = match parts.next() {
+        Some("POST") => Method::Post,
+        _ => Method::Get,
+    };
+    let path = parts.next().unwrap_or("/").to_string();
+
+    let (mut session, mut length) = (None, 0);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => length = value.parse().unwrap_or(0),
+                "cookie" => {
+                    session = value
+                        .split(';')
+                        .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                        .map(str::to_string)
+                        .next()
+                }
+                _ => {}
+            }
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Received {
+        method,
+        path,
+        session,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
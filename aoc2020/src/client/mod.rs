@@ -0,0 +1,498 @@
+//! Fetches inputs from, and submits answers to, Advent of Code
+//!
+//! Inputs are cached in the same place [`input::load_input`] reads them
+//! from, so they're only ever downloaded once. Every judged submission is
+//! logged next to them, an answer which is known to be wrong is never sent
+//! again
+//!
+//! [`input::load_input`]: crate::input::load_input
+
+pub mod http;
+pub mod mock;
+
+use self::http::{CurlTransport, Method, Request, TcpTransport, Transport};
+use crate::{input, solution::Part, Answer};
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Environment variable holding the value of the site's `session` cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable which overrides [`DEFAULT_URL`], e.g. to point at a
+/// [`MockServer`](mock::MockServer)
+pub const URL_VAR: &str = "AOC_URL";
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/naamancurtis/advent_of_code";
+
+/// What the site said about a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Judgement {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint as to which way
+    Wrong,
+    /// The answer wasn't judged as one was submitted too recently
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part has already been solved, or part 1 hasn't been yet
+    WrongLevel,
+}
+
+impl Judgement {
+    /// Reads the judgement out of the page returned after submitting an
+    /// answer, `None` if it isn't recognised
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(Judgement::Correct)
+        } else if page.contains("your answer is too high") {
+            Some(Judgement::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Some(Judgement::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Some(Judgement::Wrong)
+        } else if page.contains("You gave an answer too recently") {
+            Some(Judgement::RateLimited { wait: wait(page) })
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Judgement::WrongLevel)
+        } else {
+            None
+        }
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Judgement::TooHigh | Judgement::TooLow | Judgement::Wrong
+        )
+    }
+}
+
+/// How long the site says to wait, from e.g. `You have 1m 30s left to wait`
+fn wait(page: &str) -> Option<Duration> {
+    const PREFIX: &str = "You have ";
+    let start = page.find(PREFIX)? + PREFIX.len();
+    let end = start + page[start..].find(" left to wait")?;
+    let mut seconds = 0;
+    for amount in page[start..end].split_whitespace() {
+        seconds += match (amount.strip_suffix('m'), amount.strip_suffix('s')) {
+            (Some(minutes), _) => minutes.parse::<u64>().ok()? * 60,
+            (_, Some(s)) => s.parse::<u64>().ok()?,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+impl fmt::Display for Judgement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Judgement::Correct => write!(f, "correct"),
+            Judgement::TooHigh => write!(f, "wrong, too high"),
+            Judgement::TooLow => write!(f, "wrong, too low"),
+            Judgement::Wrong => write!(f, "wrong"),
+            Judgement::RateLimited { wait: Some(wait) } => {
+                write!(f, "submitted too recently, wait {:?}", wait)
+            }
+            Judgement::RateLimited { wait: None } => write!(f, "submitted too recently"),
+            Judgement::WrongLevel => write!(f, "already solved, or part 1 isn't yet"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    /// The answer was sent, and this is what the site said
+    Sent(Judgement),
+    /// The answer wasn't sent, as an earlier submission shows it'd be judged
+    /// like this
+    Known(Judgement),
+}
+
+impl fmt::Display for Submission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Submission::Sent(judgement) => write!(f, "{}", judgement),
+            Submission::Known(judgement) => write!(f, "{} (not resubmitted)", judgement),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct LogEntry {
+    day: u8,
+    part: u8,
+    answer: String,
+    judgement: Judgement,
+}
+
+/// Every judged submission for a year, stored as
+/// `{input dir}/{year}/submissions.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct SubmissionLog {
+    submissions: Vec<LogEntry>,
+}
+
+impl SubmissionLog {
+    fn path(dir: &Path, year: u16) -> PathBuf {
+        dir.join(year.to_string()).join("submissions.json")
+    }
+
+    fn load(path: &Path) -> Result<Self, ClientError> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| ClientError::Io {
+                path: path.to_path_buf(),
+                source: io::Error::new(io::ErrorKind::InvalidData, e),
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(ClientError::Io {
+                path: path.to_path_buf(),
+                source,
+            }),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), ClientError> {
+        let json = serde_json::to_string_pretty(self).expect("log should always serialize");
+        write_file(path, &json)
+    }
+
+    /// How `answer` would be judged, going by the earlier submissions
+    ///
+    /// Numbers at or beyond an answer which was too high or too low are
+    /// known to be wrong too
+    fn known(&self, day: u8, part: Part, answer: &Answer) -> Option<Judgement> {
+        let earlier: Vec<_> = self
+            .submissions
+            .iter()
+            .filter(|e| e.day == day && e.part == part.number())
+            .map(|e| (Answer::parse(&e.answer), e.judgement))
+            .collect();
+        // Any other answer is wrong once the right one is known
+        if let Some((correct, _)) = earlier.iter().find(|(_, j)| *j == Judgement::Correct) {
+            return Some(if correct == answer {
+                Judgement::Correct
+            } else {
+                Judgement::Wrong
+            });
+        }
+        earlier
+            .into_iter()
+            .find_map(|(previous, judgement)| match judgement {
+                _ if previous == *answer => Some(judgement),
+                Judgement::TooHigh if is_at_least(answer, &previous) => Some(judgement),
+                Judgement::TooLow if is_at_least(&previous, answer) => Some(judgement),
+                _ => None,
+            })
+    }
+}
+
+/// `a >= b` if they're both numbers
+fn is_at_least(a: &Answer, b: &Answer) -> bool {
+    match (a.to_string().parse::<i128>(), b.to_string().parse::<i128>()) {
+        (Ok(a), Ok(b)) => a >= b,
+        _ => false,
+    }
+}
+
+/// Returned when an input can't be fetched or an answer can't be submitted
+#[derive(Debug)]
+pub enum ClientError {
+    /// [`SESSION_VAR`] isn't set
+    MissingSession,
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Http {
+        url: String,
+        source: io::Error,
+    },
+    Status {
+        url: String,
+        status: u16,
+        body: String,
+    },
+    /// The page returned after submitting didn't say how the answer was
+    /// judged
+    UnrecognisedResponse(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "`{}` should be set to the value of your session cookie",
+                SESSION_VAR
+            ),
+            ClientError::Io { path, source } => {
+                write!(f, "failed to access `{}`: {}", path.display(), source)
+            }
+            ClientError::Http { url, source } => {
+                write!(f, "request to `{}` failed: {}", url, source)
+            }
+            ClientError::Status { url, status, body } => {
+                write!(f, "`{}` returned {}: {}", url, status, body.trim())
+            }
+            ClientError::UnrecognisedResponse(page) => {
+                write!(f, "unrecognised response to an answer: {}", page.trim())
+            }
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Io { source, .. } | ClientError::Http { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+pub struct Client {
+    url: String,
+    session: String,
+    input_dir: PathBuf,
+    transport: Box<dyn Transport>,
+}
+
+impl Client {
+    /// A client for `url`, inputs are cached in [`input::input_dir`]
+    ///
+    /// `http://` urls are spoken to directly, anything else goes through
+    /// `curl`
+    pub fn new(url: &str, session: &str) -> Self {
+        let transport: Box<dyn Transport> = if url.starts_with("http://") {
+            Box::new(TcpTransport)
+        } else {
+            Box::new(CurlTransport)
+        };
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            input_dir: input::input_dir(),
+            transport,
+        }
+    }
+
+    /// A client for [`URL_VAR`], or the real site if it isn't set, using
+    /// the session in [`SESSION_VAR`]
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = env::var(SESSION_VAR).map_err(|_| ClientError::MissingSession)?;
+        let url = env::var(URL_VAR).unwrap_or_else(|_| DEFAULT_URL.to_string());
+        Ok(Self::new(&url, session.trim()))
+    }
+
+    pub fn with_input_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.input_dir = dir.into();
+        self
+    }
+
+    pub fn with_transport(mut self, transport: impl Transport + 'static) -> Self {
+        self.transport = Box::new(transport);
+        self
+    }
+
+    /// The input for `year` and `day`, downloading it if it isn't cached
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let path = input::input_path(&self.input_dir, year, day);
+        if path.exists() {
+            return input::read_input(&path).map_err(|e| ClientError::Io {
+                path: e.path,
+                source: e.source,
+            });
+        }
+        let input = self.send(Method::Get, &format!("/{}/day/{}/input", year, day), None)?;
+        write_file(&path, &input)?;
+        Ok(input::normalise(&input))
+    }
+
+    /// Submits `answer` for `part`, unless it's known how it'll be judged
+    ///
+    /// Every answer which is judged is logged, rate limited submissions
+    /// aren't as they can be tried again
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Submission, ClientError> {
+        let log_path = SubmissionLog::path(&self.input_dir, year);
+        let mut log = SubmissionLog::load(&log_path)?;
+        if let Some(judgement) = log.known(day, part, answer) {
+            return Ok(Submission::Known(judgement));
+        }
+
+        let body = format!(
+            "level={}&answer={}",
+            part.number(),
+            http::form_encode(&answer.to_string())
+        );
+        let page = self.send(
+            Method::Post,
+            &format!("/{}/day/{}/answer", year, day),
+            Some(body),
+        )?;
+        let judgement = Judgement::parse(&page).ok_or(ClientError::UnrecognisedResponse(page))?;
+        if judgement == Judgement::Correct || judgement.is_wrong() {
+            log.submissions.push(LogEntry {
+                day,
+                part: part.number(),
+                answer: answer.to_string(),
+                judgement,
+            });
+            log.save(&log_path)?;
+        }
+        Ok(Submission::Sent(judgement))
+    }
+
+    fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<String>,
+    ) -> Result<String, ClientError> {
+        let url = format!("{}{}", self.url, path);
+        let request = Request {
+            method,
+            url: url.clone(),
+            headers: vec![
+                ("Cookie".to_string(), format!("session={}", self.session)),
+                ("User-Agent".to_string(), USER_AGENT.to_string()),
+            ],
+            body,
+        };
+        let response = self
+            .transport
+            .send(&request)
+            .map_err(|source| ClientError::Http {
+                url: url.clone(),
+                source,
+            })?;
+        if response.status != 200 {
+            return Err(ClientError::Status {
+                url,
+                status: response.status,
+                body: response.body,
+            });
+        }
+        Ok(response.body)
+    }
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), ClientError> {
+    let io_error = |source| ClientError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    fs::write(path, contents).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{mock::MockServer, *};
+    use crate::temp_dir::TempDir;
+
+    const SESSION: &str = "53616c7465645f5f";
+
+    /// A client for `server` with its own empty input directory
+    fn client(server: &MockServer, name: &str) -> (Client, TempDir) {
+        let dir = TempDir::new(&format!("client-{}", name));
+        (
+            Client::new(&server.url(), SESSION).with_input_dir(dir.to_path_buf()),
+            dir,
+        )
+    }
+
+    #[test]
+    fn downloads_inputs_once() {
+        let server = MockServer::start(SESSION).unwrap();
+        server.set_input(2020, 1, "1721\n979\n366\n");
+        let (client, dir) = client(&server, "inputs");
+
+        assert_eq!(client.input(2020, 1).unwrap(), "1721\n979\n366");
+        assert_eq!(client.input(2020, 1).unwrap(), "1721\n979\n366");
+        assert_eq!(server.received().len(), 1);
+        assert_eq!(server.received()[0].path, "/2020/day/1/input");
+        assert_eq!(server.received()[0].session.as_deref(), Some(SESSION));
+        assert_eq!(
+            fs::read_to_string(input::input_path(&dir, 2020, 1)).unwrap(),
+            "1721\n979\n366\n"
+        );
+
+        assert!(matches!(
+            client.input(2020, 2),
+            Err(ClientError::Status { status: 404, .. })
+        ));
+        let wrong_session = Client::new(&server.url(), "nope").with_input_dir(dir.to_path_buf());
+        assert!(matches!(
+            wrong_session.input(2020, 3),
+            Err(ClientError::Status { status: 400, .. })
+        ));
+    }
+
+    #[test]
+    fn never_resubmits_a_wrong_answer() {
+        let server = MockServer::start(SESSION).unwrap();
+        server.set_answer(2020, 1, Part::One, "514579");
+        let (client, _dir) = client(&server, "submit");
+        let submit = |answer: u64| client.submit(2020, 1, Part::One, &answer.into()).unwrap();
+
+        assert_eq!(submit(600000), Submission::Sent(Judgement::TooHigh));
+        assert_eq!(submit(600000), Submission::Known(Judgement::TooHigh));
+        assert_eq!(submit(700000), Submission::Known(Judgement::TooHigh));
+        assert_eq!(submit(1), Submission::Sent(Judgement::TooLow));
+        assert_eq!(server.received().len(), 2);
+        assert_eq!(server.received()[1].body, "level=1&answer=1");
+
+        server.rate_limit(1);
+        assert_eq!(
+            submit(514579),
+            Submission::Sent(Judgement::RateLimited {
+                wait: Some(Duration::from_secs(42))
+            })
+        );
+        assert_eq!(submit(514579), Submission::Sent(Judgement::Correct));
+        assert_eq!(submit(514579), Submission::Known(Judgement::Correct));
+        assert_eq!(submit(514578), Submission::Known(Judgement::Wrong));
+        assert_eq!(server.received().len(), 4);
+
+        assert_eq!(
+            client
+                .submit(2020, 1, Part::Two, &"anything".into())
+                .unwrap(),
+            Submission::Sent(Judgement::WrongLevel)
+        );
+    }
+
+    #[test]
+    fn parses_judgements() {
+        assert_eq!(
+            Judgement::parse("That's not the right answer.  If you're stuck"),
+            Some(Judgement::Wrong)
+        );
+        assert_eq!(
+            Judgement::parse("You gave an answer too recently. You have 1m 5s left to wait."),
+            Some(Judgement::RateLimited {
+                wait: Some(Duration::from_secs(65))
+            })
+        );
+        assert_eq!(Judgement::parse("<html>Something else</html>"), None);
+    }
+}
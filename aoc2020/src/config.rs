@@ -0,0 +1,196 @@
+use crate::solution::Solver;
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+use toml::{value::Table, Value};
+
+/// Overrides of each day's [`Config`], recorded as
+///
+/// ```toml
+/// [day1]
+/// target_sum = 2021
+///
+/// [day7]
+/// target = "dark olive"
+/// ```
+///
+/// Any field which isn't overridden keeps its default, so an empty file is the
+/// puzzles as they were set
+///
+/// [`Config`]: crate::solution::Solution::Config
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Overrides {
+    days: BTreeMap<u8, Table>,
+}
+
+impl Overrides {
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&contents).map_err(|message| ConfigError::Invalid {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, String> {
+        let tables: BTreeMap<String, Table> =
+            toml::from_str(contents).map_err(|e| e.to_string())?;
+        let mut days = BTreeMap::new();
+        for (key, table) in tables {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| format!("expected a table named `day<number>`, found `{}`", key))?;
+            days.insert(day, table);
+        }
+        Ok(Self { days })
+    }
+
+    /// Overrides a single field of `day`'s config from a `key=value` pair,
+    /// replacing any value it already had
+    ///
+    /// The value is read as TOML, falling back to a string if it isn't valid
+    /// TOML, so `target=shiny gold` doesn't need quoting
+    pub fn set(&mut self, day: u8, assignment: &str) -> Result<(), String> {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("expected `<key>=<value>`, found `{}`", assignment))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(format!("missing key in `{}`", assignment));
+        }
+        let value = value.trim();
+        let value = toml::from_str::<Table>(&format!("value = {}", value))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or_else(|| Value::String(value.to_string()));
+        self.days
+            .entry(day)
+            .or_default()
+            .insert(key.to_string(), value);
+        Ok(())
+    }
+
+    /// The overridden fields of `day`'s config, empty if there aren't any
+    pub fn get(&self, day: u8) -> Table {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    /// The config to run `solution` with
+    pub fn config_for(&self, solution: &dyn Solver) -> Result<Box<dyn Any>, String> {
+        let day = solution.day();
+        solution
+            .config(&self.get(day))
+            .map_err(|e| format!("invalid config for day {}: {}", day, e))
+    }
+}
+
+/// Returned when a config file can't be read or is malformed
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: PathBuf, source: io::Error },
+    Invalid { path: PathBuf, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "failed to read config `{}`: {}", path.display(), source)
+            }
+            ConfigError::Invalid { path, message } => {
+                write!(f, "invalid config `{}`: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            ConfigError::Invalid { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tables_per_day() {
+        let overrides = Overrides::parse("[day7]\ntarget = \"dark olive\"\n").unwrap();
+        assert_eq!(
+            overrides.get(7).get("target"),
+            Some(&Value::String("dark olive".to_string()))
+        );
+        assert!(overrides.get(1).is_empty());
+        assert!(Overrides::parse("[seven]\ntarget = \"a\"\n").is_err());
+        assert!(Overrides::parse("day7 = 1\n").is_err());
+    }
+
+    #[test]
+    fn set_reads_toml_or_falls_back_to_a_string() {
+        let mut overrides = Overrides::default();
+        overrides.set(1, "target_sum=1340").unwrap();
+        overrides.set(7, "target = shiny gold").unwrap();
+        overrides.set(7, "target=\"dark olive\"").unwrap();
+        assert_eq!(
+            overrides.get(1).get("target_sum"),
+            Some(&Value::Integer(1340))
+        );
+        assert_eq!(
+            overrides.get(7).get("target"),
+            Some(&Value::String("dark olive".to_string()))
+        );
+        assert!(overrides.set(1, "target_sum").is_err());
+        assert!(overrides.set(1, "=1").is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "day1", feature = "day7"))]
+    fn missing_fields_keep_their_default() {
+        use crate::{day_7, find_solution, solution::Part, Answer, YEAR};
+
+        let solution = find_solution(YEAR, 7).unwrap();
+        let config = Overrides::default().config_for(solution).unwrap();
+        assert_eq!(
+            config.downcast_ref::<day_7::Config>(),
+            Some(&day_7::Config::default())
+        );
+
+        let mut overrides = Overrides::default();
+        overrides.set(1, "target_sum=1340").unwrap();
+        let solution = find_solution(YEAR, 1).unwrap();
+        let input = solution.parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        let config = overrides.config_for(solution).unwrap();
+        assert_eq!(
+            solution.solve(Part::Two, input.as_ref(), config.as_ref()),
+            Answer::from(73867950)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "day1", feature = "day2", feature = "day9"))]
+    fn rejects_unknown_and_mistyped_fields() {
+        use crate::{find_solution, YEAR};
+
+        let mut overrides = Overrides::default();
+        overrides.set(1, "target=1340").unwrap();
+        overrides.set(9, "preamble=five").unwrap();
+        overrides.set(2, "anything=1").unwrap();
+        for day in [1, 9, 2].iter() {
+            let solution = find_solution(YEAR, *day).unwrap();
+            assert!(overrides.config_for(solution).is_err(), "day {}", day);
+        }
+    }
+}
@@ -1,34 +1,73 @@
-use std::{cmp::Ordering, collections::HashSet};
+use crate::{
+    parse::{parse_lines, parse_number, ParseError},
+    solution::{Alternative, Part, Solution},
+    Answer,
+};
+use serde::Deserialize;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+};
 
-const TARGET_SUM: u32 = 2020;
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// What the entries need to add up to
+    pub target_sum: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { target_sum: 2020 }
+    }
+}
 
 #[aoc_generator(day1)]
-pub fn generator(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .filter_map(|l| l.parse::<u32>().ok())
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    parse_lines(1, input, parse_number)
 }
 
 #[aoc(day1, part1)]
 pub fn solve_expense_report(input: &[u32]) -> u32 {
+    find_pair(input, Config::default().target_sum)
+}
+
+/// The product of the two entries which sum to `target`, `0` if there aren't any
+pub fn find_pair(input: &[u32], target: u32) -> u32 {
     let mut set = HashSet::new();
     for num in input {
-        if let Some(existing_num) = set.get(&(TARGET_SUM - num)) {
+        if let Some(existing_num) = target.checked_sub(*num).and_then(|n| set.get(&n)) {
             return *existing_num * num;
         }
-        set.insert(num);
+        set.insert(*num);
     }
-    unreachable!()
+    0
+}
+
+/// [`find_pair`] using the sorted two pointer search from part 2
+pub fn find_pair_two_pointer(input: &[u32], target: u32) -> u32 {
+    let mut input = input.to_vec();
+    input.sort_unstable();
+    helper(&input, target).map_or(0, |(a, b)| a * b)
 }
 
 #[aoc(day1, part2)]
 pub fn solve_expense_report_2(input: &[u32]) -> u32 {
+    find_triple(input, Config::default().target_sum)
+}
+
+/// The product of the three entries which sum to `target`, `0` if there
+/// aren't any
+pub fn find_triple(input: &[u32], target: u32) -> u32 {
     let mut input = input.to_vec();
     input.sort_unstable(); // nlog(n)
 
-    for i in 0..input.len() - 1 {
-        let current_target = TARGET_SUM - input[i];
+    for i in 0..input.len().saturating_sub(1) {
+        // The input is sorted, so nothing after this can fit either
+        let current_target = match target.checked_sub(input[i]) {
+            Some(current_target) => current_target,
+            None => break,
+        };
         if let Some((a, b)) = helper(&input[i + 1..], current_target) {
             return a * b * input[i];
         }
@@ -36,10 +75,36 @@ pub fn solve_expense_report_2(input: &[u32]) -> u32 {
     0
 }
 
+/// [`find_triple`] using a hash lookup for the last entry, like part 1
+pub fn find_triple_hash_set(input: &[u32], target: u32) -> u32 {
+    // Counted so an entry which appears more than once can be used again
+    let mut counts: HashMap<u32, usize> = HashMap::new();
+    for num in input {
+        *counts.entry(*num).or_default() += 1;
+    }
+    for (i, a) in input.iter().enumerate() {
+        for b in &input[i + 1..] {
+            let c = match a.checked_add(*b).and_then(|sum| target.checked_sub(sum)) {
+                Some(c) => c,
+                None => continue,
+            };
+            let needed = 1 + usize::from(c == *a) + usize::from(c == *b);
+            if counts.get(&c).is_some_and(|count| *count >= needed) {
+                return a * b * c;
+            }
+        }
+    }
+    0
+}
+
+/// Finds two different entries of the sorted `input` which sum to `target`
 fn helper(input: &[u32], target: u32) -> Option<(u32, u32)> {
+    if input.is_empty() {
+        return None;
+    }
     let mut left_ptr = 0;
     let mut right_ptr = input.len() - 1;
-    while left_ptr <= right_ptr {
+    while left_ptr < right_ptr {
         match (input[left_ptr] + input[right_ptr]).cmp(&target) {
             Ordering::Equal => return Some((input[left_ptr], input[right_ptr])),
             Ordering::Less => {
@@ -53,19 +118,47 @@ fn helper(input: &[u32], target: u32) -> Option<(u32, u32)> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day1;
 
-    const TEST_INPUT: [u32; 6] = [1721, 979, 366, 299, 675, 1456];
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<u32>;
+    type Config = Config;
 
-    #[test]
-    fn puzzle_1() {
-        assert_eq!(solve_expense_report(&TEST_INPUT), 514579);
+    const ALTERNATIVES: &'static [Alternative<Self::Input, Self::Config>] = &[
+        Alternative {
+            name: "two pointer",
+            part: Part::One,
+            solve: |input, config| find_pair_two_pointer(input, config.target_sum).into(),
+        },
+        Alternative {
+            name: "hash set",
+            part: Part::Two,
+            solve: |input, config| find_triple_hash_set(input, config.target_sum).into(),
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
     }
 
-    #[test]
-    fn puzzle_2() {
-        assert_eq!(solve_expense_report_2(&TEST_INPUT), 241861950);
+    fn part_1(input: &Self::Input, config: &Config) -> Answer {
+        find_pair(input, config.target_sum).into()
+    }
+
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        find_triple(input, config.target_sum).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_test! {
+        Day1,
+        example: "1721\n979\n366\n299\n675\n1456" => [514579, 241861950],
+        different_target: "1721\n979\n366\n299\n675\n1456", Config { target_sum: 1340 } => [0, 73867950],
+        repeated_entry: "673\n673\n673", Config { target_sum: 2019 } => [0, 304821217],
     }
 }
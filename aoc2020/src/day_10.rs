@@ -1,11 +1,14 @@
+use crate::{
+    metrics,
+    parse::{parse_lines, parse_number, ParseError},
+    solution::Solution,
+    Answer,
+};
 use std::collections::{HashMap, HashSet};
 
 #[aoc_generator(day10)]
-pub fn generator(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .filter_map(|l| l.parse::<u32>().ok())
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    parse_lines(10, input, parse_number)
 }
 
 #[aoc(day10, part1)]
@@ -23,7 +26,7 @@ pub fn puzzle_1(input: &[u32]) -> u32 {
 
     'outer: while joltage < max {
         for i in 0..3 {
-            if set.get(&(joltage + i + 1)).is_some() {
+            if set.contains(&(joltage + i + 1)) {
                 freq[i as usize] += 1;
                 joltage += i + 1;
                 continue 'outer;
@@ -48,6 +51,7 @@ pub fn puzzle_2(input: &[u32]) -> u64 {
 
 fn helper(current_joltage: u32, set: &mut HashMap<u32, Option<u64>>, max: u32) -> u64 {
     if let Some(count) = set.get(&current_joltage).copied().flatten() {
+        metrics::count("cache hits", 1);
         return count;
     }
     if current_joltage == max {
@@ -62,6 +66,7 @@ fn helper(current_joltage: u32, set: &mut HashMap<u32, Option<u64>>, max: u32) -
         let already_calculated = set.get(&new_joltage);
         if already_calculated.is_some() {
             if let Some(count) = already_calculated.copied().flatten() {
+                metrics::count("cache hits", 1);
                 permutations_to_end += count;
             } else {
                 permutations_to_end += helper(new_joltage, set, max);
@@ -72,6 +77,26 @@ fn helper(current_joltage: u32, set: &mut HashMap<u32, Option<u64>>, max: u32) -
     permutations_to_end
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Vec<u32>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -109,15 +134,9 @@ mod tests {
 10
 3";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 220);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 19208);
+    aoc_test! {
+        Day10,
+        example: "16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4" => [35, 8],
+        example_2: TEST_INPUT => [220, 19208],
     }
 }
@@ -1,24 +1,22 @@
-// (i, j) ===> NOT (x, y)
-const ADJACENT_MOVES: [(isize, isize); 8] = [
-    (-1, -1), // Top left
-    (-1, 0),  // Top
-    (-1, 1),  // Top right
-    (0, 1),   // Right
-    (1, 1),   // Bottom Right
-    (1, 0),   // Bottom
-    (1, -1),  // Bottom Left
-    (0, -1),  // Left
-];
-
-#[derive(PartialEq, Eq, Clone, Copy)]
+use crate::{
+    grid::{Grid, ADJACENT},
+    metrics,
+    parse::ParseError,
+    solution::Solution,
+    Answer,
+};
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Layout {
     EmptySeat,
     Occupied,
     Floor,
 }
 
-impl std::fmt::Debug for Layout {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let symbol = match self {
             Self::EmptySeat => 'L',
             Self::Occupied => '#',
@@ -28,132 +26,109 @@ impl std::fmt::Debug for Layout {
     }
 }
 
-impl From<char> for Layout {
-    fn from(c: char) -> Layout {
+impl TryFrom<char> for Layout {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Layout, Self::Error> {
         match c {
-            'L' => Layout::EmptySeat,
-            '#' => Layout::Occupied,
-            '.' => Layout::Floor,
-            _ => panic!("invalid input"),
+            'L' => Ok(Layout::EmptySeat),
+            '#' => Ok(Layout::Occupied),
+            '.' => Ok(Layout::Floor),
+            _ => Err("expected `L`, `#` or `.`"),
         }
     }
 }
 
 #[aoc_generator(day11)]
-pub fn generator(input: &str) -> Vec<Vec<Layout>> {
-    input
-        .lines()
-        .map(|l| l.chars().map(Layout::from).collect())
-        .collect()
+pub fn generator(input: &str) -> Result<Grid<Layout>, ParseError> {
+    Grid::parse(11, input)
 }
 
-#[aoc(day11, part1)]
-pub fn puzzle_1(input: &[Vec<Layout>]) -> i32 {
+/// Applies the seating rules until nothing changes, returning how many seats
+/// end up occupied
+///
+/// `count_occupied` counts the occupied seats which can be seen from a point,
+/// and a seat is vacated when at least `tolerance` of them are occupied
+fn simulate(
+    input: &Grid<Layout>,
+    tolerance: u32,
+    count_occupied: impl Fn(&Grid<Layout>, usize, usize) -> u32,
+) -> i32 {
     let mut input = input.to_owned();
     let mut changes = Vec::new();
     loop {
-        for i in 0..input.len() {
-            for j in 0..input[i].len() {
-                let adj_seats = check_number_of_occupied_adjacent_seats_1(&input, i, j);
-                if adj_seats >= 4 && input[i][j] == Layout::Occupied {
-                    changes.push((i, j, Layout::EmptySeat));
-                }
-                if adj_seats == 0 && input[i][j] == Layout::EmptySeat {
-                    changes.push((i, j, Layout::Occupied));
-                }
+        let _generation = metrics::span("generation");
+        for (point, seat) in input.points() {
+            let adj_seats = count_occupied(&input, point.0, point.1);
+            if adj_seats >= tolerance && *seat == Layout::Occupied {
+                changes.push((point, Layout::EmptySeat));
+            }
+            if adj_seats == 0 && *seat == Layout::EmptySeat {
+                changes.push((point, Layout::Occupied));
             }
         }
         if changes.is_empty() {
             break;
         }
-        while let Some((i, j, state)) = changes.pop() {
-            input[i][j] = state;
+        while let Some((point, state)) = changes.pop() {
+            input[point] = state;
         }
     }
     input
         .iter()
-        .map(|row| row.iter().filter(|seat| **seat == Layout::Occupied).count() as i32)
-        .sum()
+        .filter(|seat| **seat == Layout::Occupied)
+        .count() as i32
 }
 
-pub fn check_number_of_occupied_adjacent_seats_1(input: &[Vec<Layout>], i: usize, j: usize) -> u32 {
-    let mut counter = 0;
-    let i = i as isize;
-    let j = j as isize;
-
-    let col_len = input.len() as isize;
-    let row_len = input[0].len() as isize;
+#[aoc(day11, part1)]
+pub fn puzzle_1(input: &Grid<Layout>) -> i32 {
+    simulate(input, 4, check_number_of_occupied_adjacent_seats_1)
+}
 
-    for (ii, jj) in &ADJACENT_MOVES {
-        let new_i = i + ii;
-        let new_j = j + jj;
-        if new_i.is_negative() || new_j.is_negative() || new_i >= col_len || new_j >= row_len {
-            continue;
-        }
-        if input[new_i as usize][new_j as usize] == Layout::Occupied {
-            counter += 1;
-        }
-    }
-    counter
+pub fn check_number_of_occupied_adjacent_seats_1(input: &Grid<Layout>, i: usize, j: usize) -> u32 {
+    input
+        .neighbours_8((i, j))
+        .filter(|p| input[*p] == Layout::Occupied)
+        .count() as u32
 }
 
 #[aoc(day11, part2)]
-pub fn puzzle_2(input: &[Vec<Layout>]) -> i32 {
-    let mut input = input.to_owned();
-    let mut changes = Vec::new();
-    loop {
-        for i in 0..input.len() {
-            for j in 0..input[i].len() {
-                let adj_seats = check_number_of_occupied_adjacent_seats_2(&input, i, j);
-                if adj_seats >= 5 && input[i][j] == Layout::Occupied {
-                    changes.push((i, j, Layout::EmptySeat));
-                }
-                if adj_seats == 0 && input[i][j] == Layout::EmptySeat {
-                    changes.push((i, j, Layout::Occupied));
-                }
-            }
-        }
-        if changes.is_empty() {
-            break;
-        }
-        while let Some((i, j, state)) = changes.pop() {
-            input[i][j] = state;
-        }
-    }
-    input
+pub fn puzzle_2(input: &Grid<Layout>) -> i32 {
+    simulate(input, 5, check_number_of_occupied_adjacent_seats_2)
+}
+
+pub fn check_number_of_occupied_adjacent_seats_2(input: &Grid<Layout>, i: usize, j: usize) -> u32 {
+    ADJACENT
         .iter()
-        .map(|row| row.iter().filter(|seat| **seat == Layout::Occupied).count() as i32)
-        .sum()
+        .filter(|direction| {
+            // The first seat in each direction, looking over the floor
+            input
+                .ray((i, j), **direction)
+                .map(|p| input[p])
+                .find(|seat| *seat != Layout::Floor)
+                == Some(Layout::Occupied)
+        })
+        .count() as u32
 }
 
-pub fn check_number_of_occupied_adjacent_seats_2(input: &[Vec<Layout>], i: usize, j: usize) -> u32 {
-    let mut counter = 0;
-    let i = i as isize;
-    let j = j as isize;
+pub struct Day11;
 
-    let col_len = input.len() as isize;
-    let row_len = input[0].len() as isize;
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Grid<Layout>;
+    type Config = ();
 
-    'outer: for (ii, jj) in &ADJACENT_MOVES {
-        let mut new_i = i;
-        let mut new_j = j;
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
 
-        loop {
-            new_i += ii;
-            new_j += jj;
-            if new_i.is_negative() || new_j.is_negative() || new_i >= col_len || new_j >= row_len {
-                continue 'outer;
-            }
-            if input[new_i as usize][new_j as usize] != Layout::Floor {
-                break;
-            }
-        }
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
 
-        if input[new_i as usize][new_j as usize] == Layout::Occupied {
-            counter += 1;
-        }
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
     }
-    counter
 }
 
 #[cfg(test)]
@@ -164,7 +139,7 @@ mod tests {
     fn test_seat_checker_1() {
         let checking_input = "#.##.##.##
 #######.##";
-        let input = generator(checking_input);
+        let input = generator(checking_input).unwrap();
         assert_eq!(check_number_of_occupied_adjacent_seats_1(&input, 0, 6), 3);
     }
 
@@ -179,7 +154,7 @@ mod tests {
 .........
 #........
 ...#.....";
-        let input = generator(checking_input);
+        let input = generator(checking_input).unwrap();
         assert_eq!(check_number_of_occupied_adjacent_seats_2(&input, 4, 3), 8);
     }
 
@@ -194,15 +169,8 @@ LLLLLLLLLL
 L.LLLLLL.L
 L.LLLLL.LL";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 37);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 26);
+    aoc_test! {
+        Day11,
+        example: TEST_INPUT => [37, 26],
     }
 }
@@ -1,8 +1,14 @@
+use crate::{
+    parse::{parse_lines, parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
+use std::convert::TryFrom;
 use std::ops::Add;
 
 #[aoc_generator(day12)]
-pub fn generator(input: &str) -> Vec<Navigation> {
-    input.lines().map(From::from).collect()
+pub fn generator(input: &str) -> Result<Vec<Navigation>, ParseError> {
+    parse_lines(12, input, Navigation::try_from)
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -16,15 +22,23 @@ pub enum Navigation {
     Right(i32),
 }
 
-impl From<&str> for Navigation {
-    fn from(s: &str) -> Self {
-        let mut chars = s.chars();
-        let direction = chars.next().expect("input should always be valid");
-        let magnitude: i32 = chars
-            .collect::<String>()
-            .parse()
-            .expect("number input should be valid");
-        match direction {
+impl<'a> TryFrom<&'a str> for Navigation {
+    type Error = LineError<'a>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        let direction = s
+            .chars()
+            .next()
+            .ok_or_else(|| LineError::new(s, "expected an instruction"))?;
+        let (action, value) = s.split_at(direction.len_utf8());
+        let magnitude: i32 = parse_number(value)?;
+        if matches!(direction, 'L' | 'R') && magnitude % 90 != 0 {
+            return Err(LineError::new(
+                value,
+                "rotations should be a multiple of 90 degrees",
+            ));
+        }
+        Ok(match direction {
             'N' => Navigation::North(magnitude),
             'S' => Navigation::South(magnitude),
             'E' => Navigation::East(magnitude),
@@ -32,8 +46,13 @@ impl From<&str> for Navigation {
             'F' => Navigation::Forward(magnitude),
             'L' => Navigation::Left(magnitude),
             'R' => Navigation::Right(magnitude),
-            _ => panic!("invalid input"),
-        }
+            _ => {
+                return Err(LineError::new(
+                    action,
+                    "expected one of `N`, `S`, `E`, `W`, `F`, `L` or `R`",
+                ))
+            }
+        })
     }
 }
 
@@ -182,6 +201,26 @@ fn rotate(x: i32, y: i32, degrees: i32) -> (i32, i32) {
     (xx.round() as i32, yy.round() as i32)
 }
 
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Vec<Navigation>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,15 +244,8 @@ F7
 R90
 F11";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 25);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 286);
+    aoc_test! {
+        Day12,
+        example: TEST_INPUT => [25, 286],
     }
 }
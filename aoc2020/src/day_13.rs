@@ -1,23 +1,52 @@
+use crate::{
+    parse::{parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
+
 #[aoc_generator(day13)]
-pub fn generator(input: &str) -> (i32, Vec<Option<i32>>) {
-    let mut lines = input.lines();
-    let arrival_time: i32 = lines
+pub fn generator(input: &str) -> Result<(i32, Vec<Option<i32>>), ParseError> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+
+    let (i, line) = lines
         .next()
-        .expect("should contain valid time")
-        .parse()
-        .expect("arrival time should be a number");
-    let bus_ids = lines
+        .ok_or_else(|| ParseError::unexpected_end(13, 1, "expected the arrival time"))?;
+    let arrival_time: i32 =
+        parse_number(line.trim()).map_err(|e| ParseError::from_line(13, i + 1, line, e))?;
+
+    let (i, line) = lines
         .next()
-        .expect("second line should be there")
+        .ok_or_else(|| ParseError::unexpected_end(13, i + 2, "expected the bus ids"))?;
+    let bus_ids = line
+        .trim()
         .split(',')
-        .map(|n| n.parse::<i32>().ok())
-        .collect();
-    (arrival_time, bus_ids)
+        .map(|n| match n.trim() {
+            "x" => Ok(None),
+            n => match parse_number(n)? {
+                id if id <= 0 => Err(LineError::new(n, "bus ids should be positive")),
+                id => Ok(Some(id)),
+            },
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ParseError::from_line(13, i + 1, line, e))?;
+    if bus_ids.iter().all(Option::is_none) {
+        return Err(ParseError::new(
+            13,
+            i + 1,
+            line,
+            line.trim(),
+            "expected at least one bus id",
+        ));
+    }
+    Ok((arrival_time, bus_ids))
 }
 
 #[aoc(day13, part1)]
 pub fn puzzle_1((arrival_time, bus_ids): &(i32, Vec<Option<i32>>)) -> i32 {
-    let mut min = std::i32::MAX;
+    let mut min = i32::MAX;
     let mut min_bus_id = None;
     for bus_id in bus_ids.iter().filter(|id| id.is_some()) {
         if let Some(id) = bus_id {
@@ -28,7 +57,7 @@ pub fn puzzle_1((arrival_time, bus_ids): &(i32, Vec<Option<i32>>)) -> i32 {
             }
         }
     }
-    min * min_bus_id.unwrap()
+    min * min_bus_id.expect("the generator checks there's a bus")
 }
 
 #[aoc(day13, part2)]
@@ -52,6 +81,26 @@ pub fn puzzle_2((_, bus_ids): &(i32, Vec<Option<i32>>)) -> u64 {
         .0 as u64
 }
 
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = (i32, Vec<Option<i32>>);
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,14 +109,24 @@ mod tests {
 7,13,x,x,59,x,31,19";
 
     #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 295);
+    fn generator_reports_missing_bus_ids() {
+        let error = generator("939\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(error.text.is_empty());
     }
 
     #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 1068781);
+    fn generator_reports_no_buses() {
+        let error = generator("939\nx,x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "x,x");
+    }
+
+    aoc_test! {
+        Day13,
+        example: TEST_INPUT => [295, 1068781],
+        example_2: "939\n17,x,13,19" => [_, 3417],
+        example_3: "939\n67,7,59,61" => [_, 754018],
+        example_4: "939\n1789,37,47,1889" => [_, 1202161486],
     }
 }
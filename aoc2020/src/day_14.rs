@@ -1,47 +1,77 @@
+use crate::{
+    parse::{parse_lines, parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
 lazy_static! {
     static ref REGEX: Regex =
-        Regex::new(r"mem\[(?P<mem>\d+)\]\s=\s(?P<val>\d+)").expect("regex should be fine");
+        Regex::new(r"^mem\[(?P<mem>\d+)\] = (?P<val>\d+)$").expect("regex should be fine");
 }
 
 type PuzzleData = Vec<(String, Vec<(usize, usize)>)>;
 
+enum Instruction<'a> {
+    Mask(&'a str),
+    Write(usize, usize),
+}
+
+fn parse_instruction(line: &str) -> Result<Instruction<'_>, LineError<'_>> {
+    if let Some(mask) = line.strip_prefix("mask = ") {
+        if let Some((i, c)) = mask
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '0' | '1' | 'X'))
+        {
+            return Err(LineError::new(
+                &mask[i..i + c.len_utf8()],
+                "expected `0`, `1` or `X`",
+            ));
+        }
+        if mask.len() != 36 {
+            return Err(LineError::new(mask, "expected a 36 bit mask"));
+        }
+        return Ok(Instruction::Mask(mask));
+    }
+    let captures = REGEX.captures(line).ok_or_else(|| {
+        LineError::new(line, "expected `mask = <mask>` or `mem[<addr>] = <value>`")
+    })?;
+    let addr = parse_number(
+        captures
+            .name("mem")
+            .expect("group is not optional")
+            .as_str(),
+    )?;
+    let value = parse_number(
+        captures
+            .name("val")
+            .expect("group is not optional")
+            .as_str(),
+    )?;
+    Ok(Instruction::Write(addr, value))
+}
+
 #[aoc_generator(day14)]
-pub fn generator(input: &str) -> PuzzleData {
+pub fn generator(input: &str) -> Result<PuzzleData, ParseError> {
     let mut result = Vec::new();
     let mut mask: String = String::default();
     let mut operations = Vec::new();
-    for line in input.lines() {
-        if line.contains("mask") {
-            if !operations.is_empty() {
-                result.push((mask.clone(), operations));
-                operations = Vec::new();
-            }
-            if let Some(m) = line.split("= ").last() {
+    for instruction in parse_lines(14, input, parse_instruction)? {
+        match instruction {
+            Instruction::Mask(m) => {
+                if !operations.is_empty() {
+                    result.push((mask.clone(), operations));
+                    operations = Vec::new();
+                }
                 mask = m.to_owned();
             }
-            continue;
+            Instruction::Write(addr, value) => operations.push((addr, value)),
         }
-        let captures = REGEX.captures(line).expect("should always find match");
-        let addr: usize = captures
-            .get(1)
-            .expect("address should exist")
-            .as_str()
-            .parse()
-            .expect("addr should be number");
-        let value: usize = captures
-            .get(2)
-            .expect("value should exist")
-            .as_str()
-            .parse()
-            .expect("value should be number");
-        operations.push((addr, value));
     }
     result.push((mask, operations));
-    result
+    Ok(result)
 }
 
 #[aoc(day14, part1)]
@@ -143,6 +173,26 @@ fn heaps_algorithm(input: &mut Vec<usize>, n: usize, output: &mut Vec<Vec<usize>
     }
 }
 
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = PuzzleData;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -175,20 +225,23 @@ mem[8] = 11
 mem[7] = 101
 mem[8] = 0";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 165);
-    }
-
     const TEST_INPUT_2: &str = "mask = 000000000000000000000000000000X1001X
 mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
 mem[26] = 1";
 
     #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT_2);
-        assert_eq!(puzzle_2(&input), 208);
+    fn generator_rejects_trailing_text() {
+        let mask = "X".repeat(36);
+        for line in &["mem[8] = 11junk", "xmem[8] = 11"] {
+            let error = generator(&format!("mask = {}\n{}", mask, line)).unwrap_err();
+            assert_eq!(error.line, 2, "{}", line);
+        }
+    }
+
+    aoc_test! {
+        Day14,
+        example: TEST_INPUT => [165],
+        example_2: TEST_INPUT_2 => [_, 208],
     }
 }
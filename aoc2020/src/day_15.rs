@@ -1,24 +1,62 @@
+use crate::{
+    parse::{parse_lines, parse_number, ParseError},
+    solution::Solution,
+    Answer,
+};
+use serde::Deserialize;
 use std::collections::HashMap;
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Which number spoken is the answer to part 1
+    pub part_1_turns: u32,
+    /// Which number spoken is the answer to part 2
+    pub part_2_turns: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            part_1_turns: 2020,
+            part_2_turns: 30000000,
+        }
+    }
+}
+
 #[aoc_generator(day15)]
-pub fn generator(input: &str) -> Vec<u32> {
-    input
-        .split(',')
-        .filter_map(|n| n.parse::<u32>().ok())
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<u32>, ParseError> {
+    let numbers: Vec<u32> = parse_lines(15, input, |line| {
+        line.split(',')
+            .map(|n| parse_number(n.trim()))
+            .collect::<Result<Vec<_>, _>>()
+    })?
+    .concat();
+    if numbers.is_empty() {
+        return Err(ParseError::unexpected_end(
+            15,
+            input.lines().count() + 1,
+            "expected the starting numbers",
+        ));
+    }
+    Ok(numbers)
 }
 
 #[aoc(day15, part1)]
 pub fn puzzle_1(input: &[u32]) -> u32 {
-    solution(input, 2020)
+    solution(input, Config::default().part_1_turns)
 }
 
 #[aoc(day15, part2)]
 pub fn puzzle_2(input: &[u32]) -> u32 {
-    solution(input, 30000000)
+    solution(input, Config::default().part_2_turns)
 }
 
-fn solution(input: &[u32], target: u32) -> u32 {
+/// The number spoken on turn `target`
+pub fn solution(input: &[u32], target: u32) -> u32 {
+    if target as usize <= input.len() {
+        return input[target.max(1) as usize - 1];
+    }
     let mut counter = input.len() as u32;
     let mut last_number = input[counter as usize - 1];
     let mut cache = input
@@ -48,27 +86,38 @@ fn solution(input: &[u32], target: u32) -> u32 {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Day15;
 
-    const TEST_INPUT: &str = "0,3,6";
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<u32>;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 436);
+    fn part_1(input: &Self::Input, config: &Config) -> Answer {
+        solution(input, config.part_1_turns).into()
     }
 
-    #[test]
-    fn puzzle_1_sol() {
-        let input = generator("0,1,4,13,15,12,16");
-        assert_eq!(puzzle_1(&input), 1665);
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        solution(input, config.part_2_turns).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "0,3,6";
 
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 175594);
+    aoc_test! {
+        Day15,
+        example: TEST_INPUT => [436, 175594],
+        example_2: "1,3,2" => [1],
+        example_3: "2,1,3" => [10],
+        example_4: "3,1,2" => [1836],
+        fewer_turns: TEST_INPUT, Config { part_1_turns: 2, part_2_turns: 9 } => [3, 4],
     }
 }
@@ -1,5 +1,12 @@
+use crate::{
+    metrics,
+    parse::{parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 
 lazy_static! {
@@ -7,6 +14,21 @@ lazy_static! {
         .expect("regex should be fine");
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Fields starting with this are multiplied together in part 2
+    pub prefix: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            prefix: "departure".to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Input {
     pub rules: HashMap<String, Vec<usize>>,
@@ -15,45 +37,87 @@ pub struct Input {
 }
 
 #[aoc_generator(day16)]
-pub fn generator(input: &str) -> Input {
-    let mut new_line_counter = 0;
+pub fn generator(input: &str) -> Result<Input, ParseError> {
     let mut data = Input::default();
-    for line in input.lines() {
-        if line.is_empty() || (new_line_counter % 2 != 0 && new_line_counter != 0) {
-            new_line_counter += 1;
-            continue;
-        }
-
-        if new_line_counter == 0 {
-            let captures = REGEX.captures(line).expect("regex should match");
-            let mut cap = captures.iter();
-            cap.next(); // throw away the whole match
-            let field = cap
-                .next()
-                .flatten()
-                .expect("there should be a title")
-                .as_str();
-            let rules: Vec<usize> = cap
-                .filter_map(|num| num?.as_str().parse::<usize>().ok())
-                .collect();
-            data.rules.insert(field.to_string(), rules);
-            continue;
+    let mut section = Section::Rules;
+    for (i, line) in input.lines().enumerate() {
+        let to_parse_error = |e| ParseError::from_line(16, i + 1, line, e);
+        let trimmed = line.trim();
+        match trimmed {
+            "" => continue,
+            "your ticket:" => {
+                section = Section::Ticket;
+                continue;
+            }
+            "nearby tickets:" => {
+                section = Section::NearbyTickets;
+                continue;
+            }
+            _ => {}
         }
 
-        if new_line_counter == 2 {
-            data.ticket = parse_ticket(line);
-            continue;
+        match section {
+            Section::Rules => {
+                let (field, rules) = parse_rule(trimmed).map_err(to_parse_error)?;
+                data.rules.insert(field, rules);
+            }
+            Section::Ticket | Section::NearbyTickets => {
+                let ticket = parse_ticket(trimmed).map_err(to_parse_error)?;
+                if ticket.len() != data.rules.len() {
+                    return Err(to_parse_error(LineError::new(
+                        trimmed,
+                        format!("expected {} values, one for each field", data.rules.len()),
+                    )));
+                }
+                if section == Section::NearbyTickets {
+                    data.nearby_tickets.push(ticket);
+                } else if data.ticket.is_empty() {
+                    data.ticket = ticket;
+                } else {
+                    return Err(to_parse_error(LineError::new(
+                        trimmed,
+                        "expected `nearby tickets:`",
+                    )));
+                }
+            }
         }
-        data.nearby_tickets.push(parse_ticket(line));
     }
-    data
+    if data.ticket.is_empty() {
+        return Err(ParseError::unexpected_end(
+            16,
+            input.lines().count() + 1,
+            "expected `your ticket:` followed by your ticket",
+        ));
+    }
+    Ok(data)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Rules,
+    Ticket,
+    NearbyTickets,
 }
 
-pub fn parse_ticket(input: &str) -> Vec<usize> {
-    input
-        .split(',')
-        .filter_map(|v| v.parse::<usize>().ok())
-        .collect()
+fn parse_rule(line: &str) -> Result<(String, Vec<usize>), LineError<'_>> {
+    let captures = REGEX
+        .captures(line)
+        .ok_or_else(|| LineError::new(line, "expected `<field>: <min>-<max> or <min>-<max>`"))?;
+    let mut cap = captures.iter();
+    cap.next(); // throw away the whole match
+    let field = cap
+        .next()
+        .flatten()
+        .expect("group is not optional")
+        .as_str();
+    let rules = cap
+        .map(|num| parse_number(num.expect("group is not optional").as_str()))
+        .collect::<Result<_, _>>()?;
+    Ok((field.to_string(), rules))
+}
+
+pub fn parse_ticket(input: &str) -> Result<Vec<usize>, LineError<'_>> {
+    input.split(',').map(|v| parse_number(v.trim())).collect()
 }
 
 #[aoc(day16, part1)]
@@ -92,6 +156,12 @@ pub fn generate_consolidated_rules<'a>(rules: impl Iterator<Item = &'a Vec<usize
 
 #[aoc(day16, part2)]
 pub fn puzzle_2(input: &Input) -> usize {
+    multiply_fields(input, &Config::default().prefix)
+}
+
+/// The product of the values on your ticket of every field starting with
+/// `prefix`
+pub fn multiply_fields(input: &Input, prefix: &str) -> usize {
     let rules = input.rules.values();
     let len = rules.len();
     let consolidated_rules = generate_consolidated_rules(rules);
@@ -133,6 +203,7 @@ pub fn puzzle_2(input: &Input) -> usize {
                     }
                 }
             }
+            metrics::count("eliminations", fields_to_remove.len() as u64);
             for field in fields_to_remove.into_iter() {
                 candidates[i].remove(field);
             }
@@ -142,8 +213,8 @@ pub fn puzzle_2(input: &Input) -> usize {
             if candidates[i].len() == 1 {
                 let field = (**(candidates[i].iter().next().expect("we know len is 1"))).clone();
                 for (j, c) in candidates.iter_mut().enumerate() {
-                    if i != j {
-                        c.remove(&field);
+                    if i != j && c.remove(&field) {
+                        metrics::count("eliminations", 1);
                     }
                 }
             }
@@ -154,7 +225,7 @@ pub fn puzzle_2(input: &Input) -> usize {
         .filter_map(|set| set.iter().next().map(|s| s.to_owned()))
         .enumerate()
         .filter_map(|(i, field)| {
-            if field.contains("departure") {
+            if field.starts_with(prefix) {
                 return Some(input.ticket[i]);
             }
             None
@@ -162,10 +233,29 @@ pub fn puzzle_2(input: &Input) -> usize {
         .product()
 }
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &Config) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        multiply_fields(input, &config.prefix).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::get_input_from_file;
 
     const TEST_INPUT: &str = "class: 1-3 or 5-7
 row: 6-11 or 33-44
@@ -180,23 +270,22 @@ nearby tickets:
 55,2,20
 38,6,12";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 71);
-    }
+    const TEST_INPUT_2: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
 
-    #[test]
-    fn puzzle_1_sol() {
-        let input = get_input_from_file("input/2020/day16.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_1(&input), 25972);
-    }
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
 
-    #[test]
-    fn puzzle_2_sol() {
-        let input = get_input_from_file("input/2020/day16.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_2(&input), 622670335901);
+    aoc_test! {
+        Day16,
+        example: TEST_INPUT => [71],
+        example_2: TEST_INPUT_2, Config { prefix: "seat".to_string() } => [0, 13],
+        different_prefix: TEST_INPUT_2, Config { prefix: "c".to_string() } => [0, 12],
     }
 }
@@ -1,8 +1,23 @@
+use crate::{metrics, parse::ParseError, solution::Solution, Answer};
+use serde::Deserialize;
 use std::cmp::{Eq, PartialEq};
 use std::collections::HashSet;
 
 const DIRS: [i32; 3] = [-1, 0, 1];
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// How many cycles to simulate before counting the active cubes
+    pub cycles: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { cycles: 6 }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
 pub struct Cube {
     w: i32,
@@ -75,14 +90,23 @@ impl GenerateNeighbours for FourthDimension {
 }
 
 #[aoc_generator(day17)]
-pub fn generator(input: &str) -> HashSet<Cube> {
+pub fn generator(input: &str) -> Result<HashSet<Cube>, ParseError> {
     let mut grid = HashSet::default();
+    let line_count = input.lines().count();
     for (y, line) in input.lines().rev().enumerate() {
-        for (i, c) in line.chars().enumerate() {
+        for (i, (j, c)) in line.char_indices().enumerate() {
             let is_active = match c {
                 '.' => false,
                 '#' => true,
-                _ => panic!("invalid input"),
+                _ => {
+                    return Err(ParseError::new(
+                        17,
+                        line_count - y,
+                        line,
+                        &line[j..j + c.len_utf8()],
+                        "expected `.` or `#`",
+                    ))
+                }
             };
             if is_active {
                 let cube = Cube {
@@ -95,22 +119,23 @@ pub fn generator(input: &str) -> HashSet<Cube> {
             }
         }
     }
-    grid
+    Ok(grid)
 }
 
 #[aoc(day17, part1)]
 pub fn puzzle_1(input: &HashSet<Cube>) -> usize {
-    solution::<ThirdDimension>(input)
+    solution::<ThirdDimension>(input, Config::default().cycles)
 }
 
 #[aoc(day17, part2)]
 pub fn puzzle_2(input: &HashSet<Cube>) -> usize {
-    solution::<FourthDimension>(input)
+    solution::<FourthDimension>(input, Config::default().cycles)
 }
 
-fn solution<T: GenerateNeighbours>(input: &HashSet<Cube>) -> usize {
+fn solution<T: GenerateNeighbours>(input: &HashSet<Cube>, cycles: usize) -> usize {
     let mut map = input.to_owned();
-    for _ in 1..=6 {
+    for _ in 1..=cycles {
+        let _cycle = metrics::span("cycle");
         let mut temp_map = HashSet::new();
         std::mem::swap(&mut map, &mut temp_map);
 
@@ -131,41 +156,40 @@ fn solution<T: GenerateNeighbours>(input: &HashSet<Cube>) -> usize {
             }
         }
     }
-    map.into_iter().count()
+    map.len()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::get_input_from_file;
+pub struct Day17;
 
-    const TEST_INPUT: &str = ".#.
-..#
-###";
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = HashSet<Cube>;
+    type Config = Config;
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 112);
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
     }
 
-    #[test]
-    fn puzzle_1_sol() {
-        let input = get_input_from_file("input/2020/day17.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_1(&input), 359);
+    fn part_1(input: &Self::Input, config: &Config) -> Answer {
+        solution::<ThirdDimension>(input, config.cycles).into()
     }
 
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 848);
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        solution::<FourthDimension>(input, config.cycles).into()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = ".#.
+..#
+###";
 
-    #[test]
-    fn puzzle_2_sol() {
-        let input = get_input_from_file("input/2020/day17.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_2(&input), 2228);
+    aoc_test! {
+        Day17,
+        example: TEST_INPUT => [112, 848],
+        one_cycle: TEST_INPUT, Config { cycles: 1 } => [11, 29],
     }
 }
@@ -1,3 +1,9 @@
+use crate::{
+    parse::{parse_lines, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
+
 pub enum MathOp {
     Add,
     Multiply,
@@ -5,11 +11,54 @@ pub enum MathOp {
 }
 
 #[aoc_generator(day18)]
-pub fn generator(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|l| l.replace(" ", "").chars().collect())
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    parse_lines(18, input, |l| {
+        validate_equation(l)?;
+        Ok(l.replace(' ', "").chars().collect())
+    })
+}
+
+/// Checks numbers and operators alternate, numbers are single digits and all
+/// parens are balanced, which the solvers rely on
+fn validate_equation(equation: &str) -> Result<(), LineError<'_>> {
+    let mut open_parens = Vec::new();
+    let mut expecting_number = true;
+    for (i, c) in equation.char_indices() {
+        let token = &equation[i..i + c.len_utf8()];
+        match c {
+            ' ' => continue,
+            '(' | '0'..='9' if !expecting_number => {
+                return Err(LineError::new(token, "expected `+`, `*` or `)`"))
+            }
+            ')' | '+' | '*' if expecting_number => {
+                return Err(LineError::new(token, "expected a digit or `(`"))
+            }
+            '(' => open_parens.push(token),
+            '0'..='9' => expecting_number = false,
+            ')' => {
+                open_parens
+                    .pop()
+                    .ok_or_else(|| LineError::new(token, "unmatched `)`"))?;
+            }
+            '+' | '*' => expecting_number = true,
+            _ => {
+                return Err(LineError::new(
+                    token,
+                    "expected a digit, `+`, `*`, `(` or `)`",
+                ))
+            }
+        }
+    }
+    if let Some(token) = open_parens.pop() {
+        return Err(LineError::new(token, "unclosed `(`"));
+    }
+    if expecting_number {
+        return Err(LineError::new(
+            equation,
+            "equation is missing a final number",
+        ));
+    }
+    Ok(())
 }
 
 #[aoc(day18, part1)]
@@ -48,7 +97,7 @@ pub fn do_fake_math_1(equation: &[char]) -> u64 {
             }
             '+' => current_op = MathOp::Add,
             '*' => current_op = MathOp::Multiply,
-            n if n.is_digit(10) => {
+            n if n.is_ascii_digit() => {
                 let n = n.to_digit(10).expect("already checked it is a digit") as u64;
                 match current_op {
                     MathOp::Add => sum += n,
@@ -132,39 +181,37 @@ pub fn do_fake_math_2(equation: &mut [String]) -> u64 {
         .product()
 }
 
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+    type Input = Vec<Vec<char>>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::get_input_from_file;
 
     const TEST_INPUT: &str = "2 * 3 + (4 * 5)
     5 + (8 * 3 + 9 + 3 * 4 * 3)
     5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))
     ((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 26 + 437 + 12240 + 13632);
-    }
-
-    #[test]
-    fn puzzle_1_sol() {
-        let input = get_input_from_file("input/2020/day18.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_1(&input), 701339185745);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 46 + 1445 + 669060 + 23340);
-    }
-
-    #[test]
-    fn puzzle_2_sol() {
-        let input = get_input_from_file("input/2020/day18.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_2(&input), 4208490449905);
+    aoc_test! {
+        Day18,
+        example: TEST_INPUT => [(26 + 437 + 12240 + 13632), (46 + 1445 + 669060 + 23340)],
     }
 }
@@ -1,3 +1,9 @@
+use crate::{
+    metrics,
+    parse::{parse_number, LineError, ParseError},
+    solution::{Alternative, Part, Solution},
+    Answer,
+};
 use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone)]
@@ -8,50 +14,98 @@ pub enum Rule {
 }
 
 impl Rule {
-    pub fn new(s: &str) -> Rule {
+    pub fn new(s: &str) -> Result<Rule, LineError<'_>> {
         if s.contains('"') {
-            let c = s
-                .replace('"', "")
-                .chars()
-                .next()
-                .expect("valid letter rule should only be 1 char");
-            return Self::Letter(c);
+            let letter = s
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .filter(|letter| letter.chars().count() == 1)
+                .ok_or_else(|| LineError::new(s, "expected a single letter in quotes"))?;
+            return Ok(Self::Letter(letter.chars().next().expect("checked length")));
         }
 
-        let sub_rules: HashSet<_> = s
-            .split(" | ")
+        let sub_rules = s
+            .split('|')
             .map(|rules| {
+                if rules.trim().is_empty() {
+                    return Err(LineError::new(rules, "expected a list of rule numbers"));
+                }
                 rules
-                    .trim()
                     .split_whitespace()
-                    .filter_map(|r| r.parse::<usize>().ok())
-                    .collect::<Vec<_>>()
+                    .map(parse_number)
+                    .collect::<Result<Vec<_>, _>>()
             })
-            .collect();
-        Self::Ptr(sub_rules)
+            .collect::<Result<HashSet<_>, _>>()?;
+        Ok(Self::Ptr(sub_rules))
     }
 }
 
 #[aoc_generator(day19)]
-pub fn generator(input: &str) -> (Vec<Rule>, Vec<String>) {
+pub fn generator(input: &str) -> Result<(Vec<Rule>, Vec<String>), ParseError> {
     let mut rules = vec![Rule::Null; 150];
-    let mut iter = input.lines();
-    while let Some(line) = iter.next() {
+    // Rules can refer to ones defined later on, so these are checked at the end
+    let mut references = Vec::new();
+    let mut line_count = 0;
+    let mut iter = input.lines().enumerate();
+    for (i, line) in iter.by_ref() {
+        line_count = i + 1;
         if line.is_empty() {
             break;
         }
-        let mut split = line.split(": ");
-        let rule_num = split
-            .next()
-            .expect("rule number should be there")
-            .parse::<usize>()
-            .expect("rule number should be a number");
-
-        let rule = Rule::new(split.next().expect("rules should exist"));
+        let to_parse_error = |e| ParseError::from_line(19, i + 1, line, e);
+        let (rule_num, rule_text) = line
+            .split_once(':')
+            .ok_or_else(|| to_parse_error(LineError::new(line, "expected `<number>: <rule>`")))?;
+        let rule_num: usize = parse_number(rule_num.trim()).map_err(to_parse_error)?;
+        let rule = Rule::new(rule_text.trim()).map_err(to_parse_error)?;
+        if let Rule::Ptr(_) = rule {
+            for token in rule_text.split_whitespace().filter(|t| *t != "|") {
+                let number: usize = parse_number(token).map_err(to_parse_error)?;
+                references.push((i + 1, line, token, number));
+            }
+        }
 
+        if rule_num >= rules.len() {
+            rules.resize(rule_num + 1, Rule::Null);
+        }
         rules[rule_num] = rule;
     }
-    (rules, iter.map(|s| s.to_string()).collect())
+
+    if let Rule::Null = rules[0] {
+        return Err(ParseError::unexpected_end(
+            19,
+            line_count,
+            "expected rule 0",
+        ));
+    }
+    for (line_number, line, token, number) in references {
+        if let None | Some(Rule::Null) = rules.get(number) {
+            return Err(ParseError::new(
+                19,
+                line_number,
+                line,
+                token,
+                format!("rule {} is never defined", number),
+            ));
+        }
+    }
+
+    let mut messages = Vec::new();
+    for (i, line) in iter {
+        // Messages are matched by slicing them, which needs every character
+        // to be a single byte
+        if let Some((j, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(ParseError::new(
+                19,
+                i + 1,
+                line,
+                &line[j..j + c.len_utf8()],
+                "expected an ASCII character",
+            ));
+        }
+        messages.push(line.to_string());
+    }
+    Ok((rules, messages))
 }
 
 #[aoc(day19, part1)]
@@ -60,13 +114,10 @@ pub fn puzzle_1((rules, messages): &(Vec<Rule>, Vec<String>)) -> usize {
         .into_iter()
         .map(|s| s.into_iter().collect::<String>())
         .collect();
-    for msg in &valid_messages {
-        println!("{:?}", msg);
-    }
 
     messages
         .iter()
-        .filter(|m| valid_messages.get(*m).is_some())
+        .filter(|m| valid_messages.contains(*m))
         .count()
 }
 
@@ -96,13 +147,23 @@ pub fn generate_valid_messages(
             }
             permutations = temp;
         }
-        Rule::Null => {
-            eprintln!("Rule doesn't exist");
-        }
+        // The generator rejects references to rules which aren't defined, so
+        // this is only reachable through part 2's replacement rules
+        Rule::Null => return Vec::new(),
     }
     permutations
 }
 
+/// Part 1 checking each message against the rules, rather than listing every
+/// message they match
+pub fn puzzle_1_memoised((rules, messages): &(Vec<Rule>, Vec<String>)) -> usize {
+    let mut cache = HashMap::new();
+    messages
+        .iter()
+        .filter(|m| matches(rules, 0, m, &mut cache))
+        .count()
+}
+
 #[aoc(day19, part2)]
 pub fn puzzle_2((rules, messages): &(Vec<Rule>, Vec<String>)) -> usize {
     let mut rules = rules.to_owned();
@@ -123,17 +184,17 @@ pub fn matches(
     cache: &mut HashMap<(usize, String), bool>,
 ) -> bool {
     if let Some(&result) = cache.get(&(rule, string.to_string())) {
+        metrics::count("cache hits", 1);
         return result;
     }
+    metrics::count("cache misses", 1);
     let result = match &rules[rule] {
         Rule::Letter(c) => c.to_string() == string,
         Rule::Ptr(sub_rules) => sub_rules
             .iter()
             .any(|sub_rule| matches_sub_rule(rules, sub_rule, string, cache)),
-        Rule::Null => {
-            eprintln!("Some weird error");
-            false
-        }
+        // Nothing matches a rule which isn't defined
+        Rule::Null => false,
     };
 
     cache.insert((rule, string.to_string()), result);
@@ -164,10 +225,35 @@ pub fn matches_sub_rule(
     false
 }
 
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+    type Input = (Vec<Rule>, Vec<String>);
+    type Config = ();
+
+    const ALTERNATIVES: &'static [Alternative<Self::Input, ()>] = &[Alternative {
+        name: "memoised",
+        part: Part::One,
+        solve: |input, _| puzzle_1_memoised(input).into(),
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::get_input_from_file;
 
     const TEST_INPUT: &str = "0: 4 1 5
 1: 2 3 | 3 2
@@ -182,19 +268,6 @@ abbbab
 aaabbb
 aaaabbb";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 2);
-    }
-
-    #[test]
-    fn puzzle_1_sol() {
-        let input = get_input_from_file("input/2020/day19.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_1(&input), 160);
-    }
-
     const TEST_INPUT_2: &str = "42: 9 14 | 10 1
 9: 14 27 | 1 26
 10: 23 14 | 28 1
@@ -244,15 +317,15 @@ babaaabbbaaabaababbaabababaaab
 aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
 
     #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT_2);
-        assert_eq!(puzzle_2(&input), 12);
+    fn generator_rejects_non_ascii_messages() {
+        let error = generator("0: 1\n1: \"a\"\n\na\naé").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 2));
+        assert_eq!(error.text, "é");
     }
 
-    #[test]
-    fn puzzle_2_sol() {
-        let input = get_input_from_file("input/2020/day19.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_2(&input), 357);
+    aoc_test! {
+        Day19,
+        example: TEST_INPUT => [2],
+        example_2: TEST_INPUT_2 => [3, 12],
     }
 }
@@ -1,13 +1,19 @@
+use crate::{
+    parse::{parse_lines, parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
 
 lazy_static! {
     static ref REGEX: Regex = Regex::new(
-        r"(?P<min>\d+)-(?P<max>\d+)\s(?P<letter>[[:alpha:]]):\s(?P<password>[[:alpha:]]+)"
+        r"^(?P<min>\d+)-(?P<max>\d+) (?P<letter>[[:alpha:]]): (?P<password>[[:alpha:]]+)$"
     )
     .expect("regex should be fine");
 }
 
+#[derive(Debug)]
 pub struct PasswordDetails {
     pub min: i8,
     pub max: i8,
@@ -43,26 +49,38 @@ impl PasswordDetails {
 }
 
 #[aoc_generator(day2)]
-pub fn generator(input: &str) -> Vec<PasswordDetails> {
-    input
-        .lines()
-        .filter_map(|l| {
-            let captures = REGEX.captures(l)?;
-            let min: i8 = captures.name("min")?.as_str().parse().ok()?;
-            let max: i8 = captures.name("max")?.as_str().parse().ok()?;
-            let letter = captures.name("letter")?.as_str().chars().next()?;
-            let password = captures.name("password")?.as_str().to_string();
-
-            Some({
-                PasswordDetails {
-                    min,
-                    max,
-                    letter,
-                    password,
-                }
-            })
+pub fn generator(input: &str) -> Result<Vec<PasswordDetails>, ParseError> {
+    parse_lines(2, input, |l| {
+        let captures = REGEX
+            .captures(l)
+            .ok_or_else(|| LineError::new(l, "expected `<min>-<max> <letter>: <password>`"))?;
+        let min = captures.name("min").expect("group is not optional");
+        let max = captures.name("max").expect("group is not optional");
+        let letter = captures.name("letter").expect("group is not optional");
+        let password = captures.name("password").expect("group is not optional");
+
+        let min_value: i8 = parse_number(min.as_str())?;
+        let max_value: i8 = parse_number(max.as_str())?;
+        if min_value < 1 {
+            return Err(LineError::new(min.as_str(), "positions start from 1"));
+        }
+        if max_value < min_value {
+            return Err(LineError::new(max.as_str(), "max is smaller than min"));
+        }
+        if max_value as usize > password.as_str().chars().count() {
+            return Err(LineError::new(
+                max.as_str(),
+                "max is past the end of the password",
+            ));
+        }
+
+        Ok(PasswordDetails {
+            min: min_value,
+            max: max_value,
+            letter: letter.as_str().chars().next().expect("group is one letter"),
+            password: password.as_str().to_string(),
         })
-        .collect()
+    })
 }
 
 #[aoc(day2, part1)]
@@ -75,22 +93,49 @@ pub fn count_valid_passwords_2(input: &[PasswordDetails]) -> u32 {
     input.iter().filter(|p| p.is_valid_2()).count() as u32
 }
 
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<PasswordDetails>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        count_valid_passwords(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        count_valid_passwords_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn puzzle_1_test() {
-        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
-        let input = generator(input);
-        assert_eq!(count_valid_passwords(&input), 2);
+    fn generator_rejects_positions_outside_the_password() {
+        let error = generator("1-3 a: abcde\n1-9 a: abc").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(error.text, "9");
+        assert_eq!(generator("0-3 a: abc").unwrap_err().text, "0");
+        assert_eq!(generator("3-2 a: abc").unwrap_err().text, "2");
     }
 
     #[test]
-    fn puzzle_2_test() {
-        let input = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc";
-        let input = generator(input);
-        assert_eq!(count_valid_passwords(&input), 2);
-        assert_eq!(count_valid_passwords_2(&input), 1);
+    fn generator_rejects_trailing_text() {
+        for line in &["1-3 a: abc1", "x1-3 a: abc"] {
+            let error = generator(&format!("1-3 a: abcde\n{}", line)).unwrap_err();
+            assert_eq!(error.line, 2, "{}", line);
+        }
+    }
+
+    aoc_test! {
+        Day2,
+        example: "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc" => [2, 1],
     }
 }
@@ -1,143 +1,700 @@
-use std::collections::{HashMap, HashSet};
+use crate::{
+    grid::{Grid, Orientation, Point},
+    image::{self, Rgb},
+    parse::{parse_number, ParseError},
+    pattern::{Match, Pattern},
+    solution::Solution,
+    Answer,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt,
+};
 
 #[aoc_generator(day20)]
-pub fn generator(input: &str) -> HashMap<usize, Photo> {
+pub fn generator(input: &str) -> Result<HashMap<usize, Photo>, ParseError> {
     let mut map = HashMap::new();
-    let mut id = 0;
-    let mut photo = Vec::new();
-    for line in input.lines() {
-        if line.is_empty() {
-            let p = Photo::new(photo);
-            map.insert(id, p);
-            id = 0;
-            photo = Vec::new();
+    // (id, line number, line) of the current tile's header
+    let mut header = None;
+    let mut headers = Vec::new();
+    let mut photo: Vec<Vec<char>> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line_error = |token, message: String| ParseError::new(20, i + 1, line, token, message);
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            if let Some(header) = header.take() {
+                insert_tile(&mut map, header, std::mem::take(&mut photo))?;
+                headers.push(header);
+            }
             continue;
         }
-        if id == 0 {
-            id = line[5..9].parse::<usize>().unwrap();
-        } else {
-            photo.push(line.chars().collect());
+
+        if header.is_none() {
+            let id = trimmed
+                .strip_prefix("Tile ")
+                .and_then(|l| l.strip_suffix(':'))
+                .ok_or_else(|| line_error(trimmed, "expected `Tile <id>:`".into()))?;
+            let id = parse_number(id).map_err(|e| ParseError::from_line(20, i + 1, line, e))?;
+            header = Some((id, i + 1, line));
+            continue;
+        }
+
+        if let Some((j, c)) = trimmed
+            .char_indices()
+            .find(|(_, c)| !matches!(c, '.' | '#'))
+        {
+            return Err(line_error(
+                &trimmed[j..j + c.len_utf8()],
+                "expected `.` or `#`".into(),
+            ));
+        }
+        if let Some(first) = photo.first() {
+            if first.len() != trimmed.len() {
+                return Err(line_error(
+                    trimmed,
+                    format!("expected {} pixels", first.len()),
+                ));
+            }
         }
+        photo.push(trimmed.chars().collect());
     }
-    map
+    if let Some(header) = header {
+        insert_tile(&mut map, header, photo)?;
+        headers.push(header);
+    }
+
+    // Checked here so part 2 can't fail on an input which parsed
+    let error = match Jigsaw::new(&map).assemble() {
+        Ok(_) => return Ok(map),
+        Err(error) => error,
+    };
+    let id = match &error {
+        AssemblyError::NoTiles => {
+            return Err(ParseError::unexpected_end(
+                20,
+                input.lines().count() + 1,
+                "expected a tile",
+            ))
+        }
+        AssemblyError::Unmatched(id) => *id,
+        AssemblyError::Stuck { unplaced, .. } => unplaced[0],
+    };
+    let (_, line_number, line) = headers
+        .into_iter()
+        .find(|(header_id, _, _)| *header_id == id)
+        .expect("every tile has a header");
+    Err(ParseError::new(
+        20,
+        line_number,
+        line,
+        line.trim(),
+        error.to_string(),
+    ))
+}
+
+fn insert_tile(
+    map: &mut HashMap<usize, Photo>,
+    (id, line_number, line): (usize, usize, &str),
+    photo: Vec<Vec<char>>,
+) -> Result<(), ParseError> {
+    let tile_error = |message| ParseError::new(20, line_number, line, line.trim(), message);
+    let width = photo.first().map_or(0, |row| row.len());
+    if photo.len() != width || width < 2 {
+        return Err(tile_error(format!(
+            "tile should be square, found {} rows of {} pixels",
+            photo.len(),
+            width
+        )));
+    }
+    if map.insert(id, Photo::new(Grid::from_rows(photo))).is_some() {
+        return Err(tile_error(format!("tile {} is defined more than once", id)));
+    }
+    Ok(())
 }
 
 #[aoc(day20, part1)]
 pub fn puzzle_1(input: &HashMap<usize, Photo>) -> usize {
-    let edge_map = generate_edge_map(input);
-    edge_map
+    corners(&generate_edge_map(input)).iter().product()
+}
+
+#[aoc(day20, part2)]
+pub fn puzzle_2(input: &HashMap<usize, Photo>) -> usize {
+    let image = Jigsaw::new(input)
+        .assemble()
+        .expect("the generator checks the tiles can be assembled")
+        .image(input);
+    let monster = sea_monster();
+    let monsters = sea_monsters(&monster, &image);
+    monster
+        .mask(&image, &monsters, 'O')
+        .iter()
+        .filter(|c| **c == '#')
+        .count()
+}
+
+pub const SEA_MONSTER: &str = concat!(
+    "                  # \n",
+    "#    ##    ##    ###\n",
+    " #  #  #  #  #  #   ",
+);
+
+pub fn sea_monster() -> Pattern<char> {
+    Pattern::parse(SEA_MONSTER).expect("the sea monster should be a valid pattern")
+}
+
+/// Every sea monster in `image`. They're all turned the same way, so only the
+/// first orientation with any is kept
+pub fn sea_monsters(monster: &Pattern<char>, image: &Grid<char>) -> Vec<Match> {
+    let mut matches = monster.find_all(image);
+    if let Some(orientation) = matches.first().map(|m| m.orientation) {
+        matches.retain(|m| m.orientation == orientation);
+    }
+    matches
+}
+
+/// The tiles with two edges which don't match any other tile
+fn corners(edge_map: &HashMap<Vec<char>, HashSet<usize>>) -> Vec<usize> {
+    let mut corners: Vec<usize> = edge_map
         .values()
         .fold(HashMap::new(), |mut acc, v| {
             // If only 1 of these edges exist
             if v.len() == 1 {
                 for key in v {
-                    let entry = acc.entry(key).or_insert(0);
+                    let entry = acc.entry(*key).or_insert(0);
                     *entry += 1;
                 }
             }
             acc
         })
-        .iter()
+        .into_iter()
         .filter_map(|(k, v)| {
             // As we add each edge twice, if everything is double counted
             // so if there are 2 edges (count == 4) which haven't been matched then it's
             // a corner
-            if *v == 4 {
-                return Some(*k);
+            if v == 4 {
+                return Some(k);
             }
             None
         })
-        .product()
+        .collect();
+    corners.sort_unstable();
+    corners
 }
 
-#[aoc(day20, part2)]
-pub fn puzzle_2(input: &HashMap<usize, Photo>) -> usize {
-    let edge_map = generate_edge_map(input);
-    let dimensions = (input.len() as f64).sqrt() as usize;
-    let mut photo_keys = vec![vec![0; dimensions]; dimensions];
-    let mut photo = vec![vec![Photo::default(); dimensions]; dimensions];
-    let mut corners: Vec<usize> = edge_map
-        .values()
-        .fold(HashMap::new(), |mut acc, v| {
-            if v.len() == 1 {
-                for key in v {
-                    let entry = acc.entry(key).or_insert(0);
-                    *entry += 1;
+/// Where every tile goes in an assembled image, and which way up it's turned
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Assembly {
+    pub tiles: Grid<(usize, Orientation)>,
+}
+
+impl Assembly {
+    /// The tiles in the top left, top right, bottom left and bottom right
+    pub fn corners(&self) -> [usize; 4] {
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+        [
+            (0, 0),
+            (0, width - 1),
+            (height - 1, 0),
+            (height - 1, width - 1),
+        ]
+        .map(|point| self.tiles[point].0)
+    }
+
+    /// The same assembly with the whole image in `orientation`
+    pub fn oriented(&self, orientation: Orientation) -> Assembly {
+        Assembly {
+            tiles: self
+                .tiles
+                .oriented(orientation)
+                .map(|(id, tile)| (*id, tile.then(orientation))),
+        }
+    }
+
+    /// Every tile with its border stripped, stitched into one image
+    pub fn image(&self, photos: &HashMap<usize, Photo>) -> Grid<char> {
+        let tiles = self.tiles.map(|(id, orientation)| {
+            let mut photo = photos[id].oriented(*orientation);
+            photo.strip_border();
+            photo.consume()
+        });
+        let tile = tiles[(0, 0)].height();
+        let (width, height) = (tiles.width() * tile, tiles.height() * tile);
+        let cells = (0..width * height)
+            .map(|k| {
+                let (row, col) = (k / width, k % width);
+                tiles[(row / tile, col / tile)][(row % tile, col % tile)]
+            })
+            .collect();
+        Grid::new(width, height, cells)
+    }
+
+    /// Every tile with its border, and a line between each of them, with any
+    /// sea monsters in the image picked out
+    pub fn picture(&self, photos: &HashMap<usize, Photo>) -> Grid<Pixel> {
+        let image = self.image(photos);
+        let monster = sea_monster();
+        let monsters = monster.highlight(&image, &sea_monsters(&monster, &image));
+
+        let tile = photos[&self.tiles[(0, 0)].0].len();
+        let step = tile + 1;
+        let (width, height) = (
+            self.tiles.width() * step + 1,
+            self.tiles.height() * step + 1,
+        );
+        let mut picture = Grid::filled(width, height, Pixel::Gridline);
+        for ((row, col), (id, orientation)) in self.tiles.points() {
+            let photo = photos[id].oriented(*orientation);
+            for ((i, j), c) in photo.data.points() {
+                let rough = *c == '#';
+                let pixel = if i == 0 || j == 0 || i == tile - 1 || j == tile - 1 {
+                    Pixel::Border { rough }
+                } else if monsters[(row * (tile - 2) + i - 1, col * (tile - 2) + j - 1)] {
+                    Pixel::Monster
+                } else if rough {
+                    Pixel::Rough
+                } else {
+                    Pixel::Water
+                };
+                picture[(row * step + 1 + i, col * step + 1 + j)] = pixel;
+            }
+        }
+        picture
+    }
+
+    /// The [`picture`] as text, with each tile's id in the line above it and
+    /// sea monsters drawn as `O`
+    ///
+    /// [`picture`]: Assembly::picture
+    pub fn to_text(&self, photos: &HashMap<usize, Photo>) -> String {
+        let picture = self.picture(photos);
+        let step = photos[&self.tiles[(0, 0)].0].len() + 1;
+        let mut text = String::new();
+        for (row, pixels) in picture.rows().enumerate() {
+            if row % step == 0 {
+                for col in 0..self.tiles.width() {
+                    let label = self
+                        .tiles
+                        .get((row / step, col))
+                        .map_or_else(String::new, |(id, _)| id.to_string());
+                    // The precision cuts off any id too long for its tile
+                    text.push_str(&format!("+{:-<width$.width$}", label, width = step - 1));
                 }
+                text.push_str("+\n");
+                continue;
             }
-            acc
-        })
-        .iter()
-        .filter_map(|(k, v)| {
-            if *v == 4 {
-                return Some(**k);
+            for pixel in pixels {
+                text.push(pixel.symbol());
             }
-            None
-        })
-        .collect();
-    let starter = corners.pop().unwrap();
-    let mut starter_photo = input.get(&starter).unwrap().clone();
-    let mut counter = 0;
-    while edge_map.get(&starter_photo.left()).unwrap().len() != 1
-        && edge_map.get(&starter_photo.top()).unwrap().len() != 1
-    {
-        if counter == 4 {
-            starter_photo.flip_horizontal();
-        } else if counter == 8 {
-            starter_photo.flip_horizontal();
-            starter_photo.flip_vertical();
-        } else if counter >= 12 {
-            eprintln!("Got some error");
-            break;
-        }
-        starter_photo.rotate_90_clockwise();
-        counter += 1;
-    }
-    photo_keys[0][0] = starter;
-    photo[0][0] = starter_photo;
-
-    for i in 0..dimensions {
-        for j in 0..dimensions {
-            // Skip starter
-            if i == j && i == 0 {
-                continue;
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The [`picture`] as a greyscale PGM image, `scale` pixels to a cell
+    ///
+    /// [`picture`]: Assembly::picture
+    pub fn to_pgm(&self, photos: &HashMap<usize, Photo>, scale: usize) -> Vec<u8> {
+        image::pgm(&image::scaled(
+            &self.picture(photos).map(|p| p.grey()),
+            scale,
+        ))
+    }
+
+    /// The [`picture`] as a colour PPM image, `scale` pixels to a cell
+    ///
+    /// [`picture`]: Assembly::picture
+    pub fn to_ppm(&self, photos: &HashMap<usize, Photo>, scale: usize) -> Vec<u8> {
+        image::ppm(&image::scaled(
+            &self.picture(photos).map(|p| p.colour()),
+            scale,
+        ))
+    }
+}
+
+/// A cell of an [`Assembly::picture`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pixel {
+    /// Between two tiles
+    Gridline,
+    /// Part of a tile's border, which isn't in the image
+    Border {
+        rough: bool,
+    },
+    Water,
+    Rough,
+    /// Part of a sea monster
+    Monster,
+}
+
+impl Pixel {
+    /// Gridlines are drawn as `|`, the rest as they are in the input
+    pub fn symbol(self) -> char {
+        match self {
+            Pixel::Gridline => '|',
+            Pixel::Border { rough: true } | Pixel::Rough => '#',
+            Pixel::Border { rough: false } | Pixel::Water => '.',
+            Pixel::Monster => 'O',
+        }
+    }
+
+    pub fn grey(self) -> u8 {
+        match self {
+            Pixel::Gridline => 0,
+            Pixel::Border { rough: false } => 70,
+            Pixel::Border { rough: true } => 120,
+            Pixel::Water => 35,
+            Pixel::Rough => 190,
+            Pixel::Monster => 255,
+        }
+    }
+
+    pub fn colour(self) -> Rgb {
+        match self {
+            Pixel::Gridline => [0, 0, 0],
+            Pixel::Border { rough: false } => [70, 70, 70],
+            Pixel::Border { rough: true } => [140, 140, 140],
+            Pixel::Water => [20, 60, 140],
+            Pixel::Rough => [225, 225, 225],
+            Pixel::Monster => [40, 200, 80],
+        }
+    }
+}
+
+/// Why the tiles couldn't be assembled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssemblyError {
+    NoTiles,
+    /// None of this tile's edges match any other tile's
+    Unmatched(usize),
+    /// The furthest any layout got before there was nowhere left to go: none
+    /// of the tiles left over fit at `position`, next to the `neighbours`
+    /// already placed around it
+    Stuck {
+        width: usize,
+        height: usize,
+        position: Point,
+        neighbours: Vec<(Side, usize)>,
+        unplaced: Vec<usize>,
+    },
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssemblyError::NoTiles => write!(f, "there are no tiles"),
+            AssemblyError::Unmatched(id) => {
+                write!(f, "tile {} doesn't share an edge with any other tile", id)
             }
+            AssemblyError::Stuck {
+                width,
+                height,
+                position: (row, col),
+                neighbours,
+                unplaced,
+            } => {
+                let neighbours: Vec<String> = neighbours
+                    .iter()
+                    .map(|(side, id)| format!("{} on the {}", id, side))
+                    .collect();
+                let unplaced: Vec<String> = unplaced.iter().map(usize::to_string).collect();
+                write!(
+                    f,
+                    "none of tiles {} fit at row {}, column {} of a {}x{} image, next to {}",
+                    unplaced.join(", "),
+                    row,
+                    col,
+                    width,
+                    height,
+                    neighbours.join(" and ")
+                )
+            }
+        }
+    }
+}
 
-            let edge_to_match = photo[i][j - 1].right();
-            let new_photo_key = edge_map
-                .get(&edge_to_match)
-                .unwrap()
-                .clone()
-                .into_iter()
-                .find(|k| !photo_keys.iter().flatten().any(|key| key == k)) // We haven't already added the tile
-                .unwrap();
-            let mut new_photo = input.get(&new_photo_key).unwrap().clone();
-            let mut counter = 0;
-            while new_photo.left() != edge_to_match {
-                if counter == 4 {
-                    new_photo.flip_horizontal();
-                } else if counter == 8 {
-                    new_photo.flip_vertical();
-                } else if counter == 12 {
-                    new_photo.flip_horizontal();
-                } else if counter >= 16 {
-                    eprintln!("Got some error");
-                    break;
+impl Error for AssemblyError {}
+
+/// Puts the tiles together by trying every way they could fit, backing up
+/// whenever a gap is left that nothing fits, so it copes with edges which
+/// match more than one other tile and with images which aren't square
+pub struct Jigsaw {
+    /// Every tile's id, smallest first
+    ids: Vec<usize>,
+    /// `edges[tile][orientation][side]`, with each distinct edge numbered
+    edges: Vec<[[usize; 4]; 8]>,
+    /// `(tile, orientation)` of every tile with that edge along that side
+    by_edge: HashMap<(Side, usize), Vec<(usize, usize)>>,
+    unmatched: Vec<usize>,
+}
+
+impl Jigsaw {
+    pub fn new(photos: &HashMap<usize, Photo>) -> Self {
+        let edge_map = generate_edge_map(photos);
+        let numbers: HashMap<&Vec<char>, usize> = edge_map
+            .keys()
+            .enumerate()
+            .map(|(number, edge)| (edge, number))
+            .collect();
+        let mut ids: Vec<usize> = photos.keys().copied().collect();
+        ids.sort_unstable();
+
+        let mut by_edge: HashMap<(Side, usize), Vec<(usize, usize)>> = HashMap::new();
+        let mut edges = Vec::with_capacity(ids.len());
+        for (tile, id) in ids.iter().enumerate() {
+            let mut tile_edges = [[0; 4]; 8];
+            for (o, orientation) in Orientation::all().enumerate() {
+                for side in Side::ALL.iter() {
+                    let number = numbers[&photos[id].edge(orientation, *side)];
+                    tile_edges[o][*side as usize] = number;
+                    by_edge.entry((*side, number)).or_default().push((tile, o));
+                }
+            }
+            edges.push(tile_edges);
+        }
+        let unmatched = ids
+            .iter()
+            .copied()
+            .filter(|id| photos[id].edges().iter().all(|e| edge_map[e].len() == 1))
+            .collect();
+        Self {
+            ids,
+            edges,
+            by_edge,
+            unmatched,
+        }
+    }
+
+    /// The first assembly found
+    pub fn assemble(&self) -> Result<Assembly, AssemblyError> {
+        self.search(1).map(|mut found| found.remove(0))
+    }
+
+    /// Every distinct assembly, with each one only turned one way
+    pub fn assemblies(&self) -> Result<Vec<Assembly>, AssemblyError> {
+        self.search(usize::MAX)
+    }
+
+    fn search(&self, limit: usize) -> Result<Vec<Assembly>, AssemblyError> {
+        let tiles = self.ids.len();
+        if tiles == 0 {
+            return Err(AssemblyError::NoTiles);
+        }
+        if tiles > 1 {
+            if let Some(id) = self.unmatched.first() {
+                return Err(AssemblyError::Unmatched(*id));
+            }
+        }
+
+        // Every turned or flipped copy of an assembly has the first tile
+        // turned a different way, so keeping it the right way up finds each
+        // assembly exactly once
+        let mut search = Search {
+            jigsaw: self,
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+            used: vec![false; tiles],
+            placed: 0,
+            limit,
+            found: Vec::new(),
+            stuck: None,
+        };
+        let mut layouts: Vec<(usize, usize)> = (1..=tiles)
+            .filter(|width| tiles.is_multiple_of(*width))
+            .map(|width| (width, tiles / width))
+            .collect();
+        // Squares first
+        layouts.sort_by_key(|(width, height)| (*width as isize - *height as isize).abs());
+        for (width, height) in layouts {
+            search.width = width;
+            search.height = height;
+            search.cells = vec![None; tiles];
+            for start in 0..tiles {
+                search.place(start, (0, 0));
+                let done = search.next();
+                search.remove(start);
+                if done {
+                    return Ok(search.found);
                 }
-                new_photo.rotate_90_clockwise();
-                counter += 1;
             }
+        }
+        match search.stuck {
+            _ if !search.found.is_empty() => Ok(search.found),
+            Some((_, error)) => Err(error),
+            // Every search which doesn't find an assembly gets stuck somewhere
+            None => unreachable!("the search gave up without getting stuck"),
+        }
+    }
+}
+
+struct Search<'a> {
+    jigsaw: &'a Jigsaw,
+    width: usize,
+    height: usize,
+    /// `(tile, orientation)` in each position, row by row
+    cells: Vec<Option<(usize, usize)>>,
+    used: Vec<bool>,
+    placed: usize,
+    limit: usize,
+    found: Vec<Assembly>,
+    /// How many tiles had been placed when the search got stuck, and where
+    stuck: Option<(usize, AssemblyError)>,
+}
+
+impl Search<'_> {
+    fn place(&mut self, cell: usize, (tile, orientation): (usize, usize)) {
+        self.cells[cell] = Some((tile, orientation));
+        self.used[tile] = true;
+        self.placed += 1;
+    }
+
+    fn remove(&mut self, cell: usize) {
+        if let Some((tile, _)) = self.cells[cell].take() {
+            self.used[tile] = false;
+            self.placed -= 1;
+        }
+    }
+
+    /// Fills the rest of the image, returns `true` once enough assemblies
+    /// have been found
+    fn next(&mut self) -> bool {
+        if self.placed == self.cells.len() {
+            self.found.push(self.assembly());
+            return self.found.len() >= self.limit;
+        }
+
+        // Fill the gap next to the tiles so far with the fewest options first,
+        // so a gap nothing fits is found as soon as possible
+        let mut best: Option<(usize, Vec<(usize, usize)>)> = None;
+        for cell in 0..self.cells.len() {
+            if self.cells[cell].is_some() {
+                continue;
+            }
+            let candidates = match self.candidates(cell) {
+                Some(candidates) => candidates,
+                None => continue,
+            };
+            if candidates.is_empty() {
+                self.record_stuck(cell);
+                return false;
+            }
+            if best
+                .as_ref()
+                .is_none_or(|(_, b)| candidates.len() < b.len())
+            {
+                best = Some((cell, candidates));
+            }
+        }
 
-            photo_keys[i][j] = new_photo_key;
-            photo[i][j] = new_photo;
+        let (cell, candidates) = best.expect("an unfinished image has gaps next to its tiles");
+        for candidate in candidates {
+            self.place(cell, candidate);
+            let done = self.next();
+            self.remove(cell);
+            if done {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The cell next to `cell` on `side`, if it's in the image
+    fn neighbour(&self, cell: usize, side: Side) -> Option<usize> {
+        let (row, col) = (cell / self.width, cell % self.width);
+        let (row, col) = match side {
+            Side::Top => (row.checked_sub(1)?, col),
+            Side::Right => (row, col + 1),
+            Side::Bottom => (row + 1, col),
+            Side::Left => (row, col.checked_sub(1)?),
+        };
+        if row < self.height && col < self.width {
+            Some(row * self.width + col)
+        } else {
+            None
+        }
+    }
+
+    /// The edge each placed neighbour of `cell` has facing it
+    fn constraints(&self, cell: usize) -> Vec<(Side, usize)> {
+        Side::ALL
+            .iter()
+            .filter_map(|side| {
+                let (tile, orientation) = self.cells[self.neighbour(cell, *side)?]?;
+                Some((
+                    *side,
+                    self.jigsaw.edges[tile][orientation][side.opposite() as usize],
+                ))
+            })
+            .collect()
+    }
+
+    /// Every unused tile, and which way up, that fits at `cell`, `None` if
+    /// there's nothing next to it yet
+    fn candidates(&self, cell: usize) -> Option<Vec<(usize, usize)>> {
+        let constraints = self.constraints(cell);
+        let (first, rest) = constraints.split_first()?;
+        let edges = &self.jigsaw.edges;
+        Some(
+            self.jigsaw
+                .by_edge
+                .get(first)
+                .into_iter()
+                .flatten()
+                .filter(|(tile, o)| {
+                    !self.used[*tile]
+                        && rest
+                            .iter()
+                            .all(|(side, edge)| edges[*tile][*o][*side as usize] == *edge)
+                })
+                .copied()
+                .collect(),
+        )
+    }
+
+    fn record_stuck(&mut self, cell: usize) {
+        if self
+            .stuck
+            .as_ref()
+            .is_some_and(|(placed, _)| *placed >= self.placed)
+        {
+            return;
+        }
+        let ids = &self.jigsaw.ids;
+        let neighbours = Side::ALL
+            .iter()
+            .filter_map(|side| {
+                let (tile, _) = self.cells[self.neighbour(cell, *side)?]?;
+                Some((*side, ids[tile]))
+            })
+            .collect();
+        let unplaced = (0..ids.len())
+            .filter(|tile| !self.used[*tile])
+            .map(|tile| ids[tile])
+            .collect();
+        let error = AssemblyError::Stuck {
+            width: self.width,
+            height: self.height,
+            position: (cell / self.width, cell % self.width),
+            neighbours,
+            unplaced,
+        };
+        self.stuck = Some((self.placed, error));
+    }
+
+    fn assembly(&self) -> Assembly {
+        let tiles = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let (tile, orientation) = cell.expect("every cell is filled");
+                (self.jigsaw.ids[tile], Orientation::ALL[orientation])
+            })
+            .collect();
+        Assembly {
+            tiles: Grid::new(self.width, self.height, tiles),
         }
-        println!("{:?}", photo_keys[0]);
-        println!("{:?}", photo[0][0].data);
-        println!("{:?}", photo[0][1].data);
-        println!("{:?}", photo[0][2].data);
     }
-    println!("{:?}", photo);
-    todo!()
 }
 
 fn generate_edge_map(input: &HashMap<usize, Photo>) -> HashMap<Vec<char>, HashSet<usize>> {
@@ -154,41 +711,101 @@ fn generate_edge_map(input: &HashMap<usize, Photo>) -> HashMap<Vec<char>, HashSe
     edge_map
 }
 
+/// A side of a [`Photo`], its edge is read left to right or top to bottom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl Side {
+    pub const ALL: [Side; 4] = [Side::Top, Side::Right, Side::Bottom, Side::Left];
+
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Right => Side::Left,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = match self {
+            Side::Top => "top",
+            Side::Right => "right",
+            Side::Bottom => "bottom",
+            Side::Left => "left",
+        };
+        f.pad(side)
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Default)]
 pub struct Photo {
-    pub data: Vec<Vec<char>>,
+    pub data: Grid<char>,
 }
 
 impl Photo {
-    pub fn new(data: Vec<Vec<char>>) -> Self {
+    pub fn new(data: Grid<char>) -> Self {
         Self { data }
     }
 
     pub fn top(&self) -> Vec<char> {
-        self.data[0].clone()
+        self.data.row(0).to_vec()
     }
 
     pub fn bottom(&self) -> Vec<char> {
-        self.data[self.data.len() - 1].clone()
+        self.data.row(self.data.height() - 1).to_vec()
     }
 
     pub fn left(&self) -> Vec<char> {
-        self.data
-            .iter()
-            .filter_map(|l| l.first().copied())
-            .collect()
+        self.data.column(0).copied().collect()
     }
 
     pub fn right(&self) -> Vec<char> {
-        self.data.iter().filter_map(|l| l.last().copied()).collect()
+        self.data.column(self.data.width() - 1).copied().collect()
     }
 
     pub fn edges(&self) -> Vec<Vec<char>> {
         vec![self.top(), self.bottom(), self.left(), self.right()]
     }
 
+    /// The edge along `side` as if the photo were in `orientation`, without
+    /// turning the whole photo
+    pub fn edge(&self, orientation: Orientation, side: Side) -> Vec<char> {
+        let (width, height) = self.data.oriented_size(orientation);
+        let length = match side {
+            Side::Top | Side::Bottom => width,
+            Side::Left | Side::Right => height,
+        };
+        (0..length)
+            .map(|i| match side {
+                Side::Top => (0, i),
+                Side::Right => (i, width - 1),
+                Side::Bottom => (height - 1, i),
+                Side::Left => (i, 0),
+            })
+            .filter_map(|point| self.data.get_oriented(orientation, point))
+            .copied()
+            .collect()
+    }
+
+    /// A copy of the photo in `orientation`
+    pub fn oriented(&self, orientation: Orientation) -> Photo {
+        Photo::new(self.data.oriented(orientation))
+    }
+
+    pub fn orient(&mut self, orientation: Orientation) {
+        self.data = self.data.oriented(orientation);
+    }
+
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.data.height()
     }
 
     pub fn is_empty(&self) -> bool {
@@ -196,55 +813,56 @@ impl Photo {
     }
 
     pub fn rotate_90_clockwise(&mut self) {
-        let len = self.len();
-        for i in 0..len / 2 {
-            for j in i..len - i - 1 {
-                let temp = self.data[i][j];
-                self.data[i][j] = self.data[j][len - 1 - j];
-                self.data[j][len - 1 - i] = self.data[len - 1 - i][len - 1 - j];
-                self.data[len - 1 - i][len - 1 - j] = self.data[len - 1 - j][i];
-                self.data[len - 1 - j][i] = temp
-            }
-        }
+        self.data.rotate_clockwise();
     }
 
     pub fn flip_horizontal(&mut self) {
-        let mut data = self.data.to_owned();
-        data = data
-            .into_iter()
-            .map(|row| row.into_iter().rev().collect())
-            .collect();
-        self.data = data
+        self.data.flip_horizontal();
     }
 
     pub fn flip_vertical(&mut self) {
-        let mut data = self.data.to_owned();
-        data = data.into_iter().rev().collect();
-        self.data = data
+        self.data.flip_vertical();
     }
 
     pub fn strip_border(&mut self) {
-        let len = self.data.len();
-        let mut data = self.data.to_owned();
-        // top
-        data.remove(0);
-        // bottom
-        data.pop();
-        self.data = data
-            .into_iter()
-            .map(|row| row.into_iter().skip(1).take(len - 2).collect())
+        let (width, height) = (self.data.width(), self.data.height());
+        let cells = self
+            .data
+            .points()
+            .filter(|((i, j), _)| *i > 0 && *j > 0 && *i < height - 1 && *j < width - 1)
+            .map(|(_, c)| *c)
             .collect();
+        self.data = Grid::new(width - 2, height - 2, cells);
     }
 
-    pub fn consume(self) -> Vec<Vec<char>> {
+    pub fn consume(self) -> Grid<char> {
         self.data
     }
 }
 
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+    type Input = HashMap<usize, Photo>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::get_input_from_file;
 
     const TEST_INPUT: &str = "Tile 2311:
 ..##.#..#.
@@ -357,28 +975,241 @@ Tile 3079:
 ";
 
     #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 20899048083289);
+    fn generator_rejects_tiles_that_arent_square() {
+        let error = generator("Tile 1:\n#.\n.#\n\nTile 2:\n##\n").unwrap_err();
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(error.text, "Tile 2:");
+    }
+
+    #[test]
+    fn generator_rejects_tiles_that_cant_be_assembled() {
+        let blank = format!("Tile 1:\n{}", "..........\n".repeat(10));
+        let input = format!("{}{}", TEST_INPUT, blank);
+        let error = generator(&input).unwrap_err();
+        assert_eq!(error.line, TEST_INPUT.lines().count() + 1);
+        assert_eq!(error.text, "Tile 1:");
+        assert_eq!(error.message, AssemblyError::Unmatched(1).to_string());
+
+        assert_eq!(generator("").unwrap_err().message, "expected a tile");
+    }
+
+    #[test]
+    fn edges_in_every_orientation() {
+        let photos = generator(TEST_INPUT).unwrap();
+        let photo = &photos[&2311];
+        for orientation in Orientation::all() {
+            let oriented = photo.oriented(orientation);
+            let edges: Vec<_> = Side::ALL
+                .iter()
+                .map(|side| photo.edge(orientation, *side))
+                .collect();
+            assert_eq!(
+                edges,
+                [
+                    oriented.top(),
+                    oriented.right(),
+                    oriented.bottom(),
+                    oriented.left()
+                ],
+                "{:?}",
+                orientation
+            );
+        }
+    }
+
+    #[test]
+    fn assembles_the_example_one_way() {
+        let photos = generator(TEST_INPUT).unwrap();
+        let assemblies = Jigsaw::new(&photos).assemblies().unwrap();
+        assert_eq!(assemblies.len(), 1);
+        let mut corners = assemblies[0].corners();
+        corners.sort_unstable();
+        assert_eq!(corners, [1171, 1951, 2971, 3079]);
+        assert_eq!(assemblies[0].image(&photos).len(), 24 * 24);
+    }
+
+    #[test]
+    fn assembles_images_which_arent_square() {
+        let mut photos = generator(TEST_INPUT).unwrap();
+        // Leaves the top two rows of the example
+        for id in [2971, 1489, 1171].iter() {
+            photos.remove(id);
+        }
+        let assemblies = Jigsaw::new(&photos).assemblies().unwrap();
+        assert_eq!(assemblies.len(), 1);
+        let tiles = &assemblies[0].tiles;
+        let mut sides = [tiles.width(), tiles.height()];
+        sides.sort_unstable();
+        assert_eq!(sides, [2, 3]);
+        let mut corners = assemblies[0].corners();
+        corners.sort_unstable();
+        assert_eq!(corners, [1951, 2473, 2729, 3079]);
+    }
+
+    fn permutations(ids: &[usize]) -> Vec<Vec<usize>> {
+        if ids.is_empty() {
+            return vec![vec![]];
+        }
+        (0..ids.len())
+            .flat_map(|i| {
+                let mut rest = ids.to_vec();
+                let first = rest.remove(i);
+                permutations(&rest).into_iter().map(move |mut order| {
+                    order.insert(0, first);
+                    order
+                })
+            })
+            .collect()
+    }
+
+    /// Every assembly found by trying every order and orientation of the
+    /// tiles, turned so the smallest tile is the right way up
+    fn brute_force(photos: &HashMap<usize, Photo>) -> HashSet<Assembly> {
+        let mut ids: Vec<usize> = photos.keys().copied().collect();
+        ids.sort_unstable();
+        let mut edges = HashMap::new();
+        for id in &ids {
+            for orientation in Orientation::all() {
+                for side in Side::ALL.iter() {
+                    let edge = photos[id].edge(orientation, *side);
+                    edges.insert((*id, orientation, *side), edge);
+                }
+            }
+        }
+        let edge =
+            |(id, orientation): &(usize, Orientation), side| &edges[&(*id, *orientation, side)];
+
+        let tiles = ids.len();
+        let mut found = HashSet::new();
+        for width in (1..=tiles).filter(|width| tiles.is_multiple_of(*width)) {
+            for order in permutations(&ids) {
+                for turns in 0..8usize.pow(tiles as u32) {
+                    let placed = order
+                        .iter()
+                        .enumerate()
+                        .map(|(i, id)| (*id, Orientation::ALL[turns / 8usize.pow(i as u32) % 8]))
+                        .collect();
+                    let grid = Grid::new(width, tiles / width, placed);
+                    let fits = grid.points().all(|((row, col), tile)| {
+                        let right = grid.get((row, col + 1));
+                        let below = grid.get((row + 1, col));
+                        right.is_none_or(|r| edge(tile, Side::Right) == edge(r, Side::Left))
+                            && below.is_none_or(|b| edge(tile, Side::Bottom) == edge(b, Side::Top))
+                    });
+                    if fits {
+                        let assembly = Assembly { tiles: grid };
+                        let (_, first) =
+                            assembly.tiles.iter().find(|(id, _)| *id == ids[0]).unwrap();
+                        found.insert(assembly.oriented(first.inverse()));
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn finds_every_assembly_of_ambiguous_tiles() {
+        use crate::gen::Rng;
+
+        let mut counts = Vec::new();
+        for seed in 0..4 {
+            let mut rng = Rng::new(seed);
+            // Tiny tiles, so plenty of edges match more than one other tile
+            let photos: HashMap<usize, Photo> = (1..=4)
+                .map(|id| {
+                    let cells = (0..9)
+                        .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                        .collect();
+                    (id, Photo::new(Grid::new(3, 3, cells)))
+                })
+                .collect();
+            let expected = brute_force(&photos);
+            let assemblies = Jigsaw::new(&photos).assemblies();
+            let found: HashSet<Assembly> = assemblies.iter().flatten().cloned().collect();
+            assert_eq!(found, expected, "seed {}", seed);
+            assert_eq!(
+                assemblies.map_or(0, |a| a.len()),
+                expected.len(),
+                "seed {}",
+                seed
+            );
+            counts.push(expected.len());
+        }
+        assert!(counts.iter().any(|count| *count > 1), "{:?}", counts);
+    }
+
+    #[test]
+    fn explains_why_tiles_dont_fit() {
+        let mut photos = generator(TEST_INPUT).unwrap();
+        photos.insert(1, Photo::new(Grid::filled(10, 10, '.')));
+        assert_eq!(
+            Jigsaw::new(&photos).assemble(),
+            Err(AssemblyError::Unmatched(1))
+        );
+
+        let mut photos = generator(TEST_INPUT).unwrap();
+        // The middle tile, leaving a gap nothing else fits
+        photos.remove(&1427);
+        match Jigsaw::new(&photos).assemble() {
+            Err(error @ AssemblyError::Stuck { .. }) => {
+                assert!(error.to_string().starts_with("none of tiles "), "{}", error)
+            }
+            other => panic!("expected to get stuck, got {:?}", other),
+        }
+        assert_eq!(
+            Jigsaw::new(&HashMap::new()).assemble(),
+            Err(AssemblyError::NoTiles)
+        );
+    }
+
+    #[test]
+    fn finds_the_example_sea_monsters() {
+        let photos = generator(TEST_INPUT).unwrap();
+        let image = Jigsaw::new(&photos).assemble().unwrap().image(&photos);
+        let monster = sea_monster();
+        let monsters = sea_monsters(&monster, &image);
+        assert_eq!(monsters.len(), 2);
+        let masked = monster.mask(&image, &monsters, 'O');
+        assert_eq!(masked.iter().filter(|c| **c == 'O').count(), 30);
     }
 
     #[test]
-    fn puzzle_1_sol() {
-        let input = get_input_from_file("input/2020/day20.txt");
-        let input = generator(&input);
-        assert_eq!(puzzle_1(&input), 29125888761511);
-    }
-
-    // #[test]
-    // fn puzzle_2_test() {
-    //     let input = generator(TEST_INPUT);
-    //     assert_eq!(puzzle_2(&input), 12);
-    // }
-
-    // #[test]
-    // fn puzzle_2_sol() {
-    //     let input = get_input_from_file("input/2020/day20.txt");
-    //     let input = generator(&input);
-    //     assert_eq!(puzzle_2(&input), 357);
-    // }
+    fn draws_the_example_picture() {
+        let photos = generator(TEST_INPUT).unwrap();
+        let assembly = Jigsaw::new(&photos).assemble().unwrap();
+        let text = assembly.to_text(&photos);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 34);
+        assert!(lines.iter().all(|line| line.len() == 34));
+        assert_eq!(lines[33], "+----------+----------+----------+");
+        for id in photos.keys() {
+            assert_eq!(text.matches(&id.to_string()).count(), 1, "{}", id);
+        }
+        assert_eq!(text.matches('O').count(), 30);
+
+        let header = b"P5\n68 68\n255\n";
+        let pgm = assembly.to_pgm(&photos, 2);
+        assert_eq!(&pgm[..header.len()], header);
+        assert_eq!(pgm.len(), header.len() + 68 * 68);
+        let ppm = assembly.to_ppm(&photos, 1);
+        assert_eq!(ppm.len(), b"P6\n34 34\n255\n".len() + 34 * 34 * 3);
+    }
+
+    #[test]
+    fn cuts_off_ids_wider_than_a_tile() {
+        let photos: HashMap<usize, Photo> = generator(TEST_INPUT)
+            .unwrap()
+            .into_iter()
+            .map(|(id, photo)| (id * 10_000_000, photo))
+            .collect();
+        let text = Jigsaw::new(&photos).assemble().unwrap().to_text(&photos);
+        assert!(text.lines().all(|line| line.len() == 34), "{}", text);
+        assert!(text.contains("+2311000000+"), "{}", text);
+    }
+
+    aoc_test! {
+        Day20,
+        example: TEST_INPUT => [20899048083289, 273],
+    }
 }
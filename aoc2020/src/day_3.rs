@@ -1,3 +1,6 @@
+use crate::{grid::Grid, parse::ParseError, solution::Solution, Answer};
+use std::convert::TryFrom;
+use std::fmt;
 use std::sync::{mpsc, Arc};
 use std::thread;
 
@@ -7,48 +10,44 @@ pub enum Square {
     Tree,
 }
 
-impl From<char> for Square {
-    fn from(c: char) -> Self {
+impl TryFrom<char> for Square {
+    type Error = &'static str;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
         match c {
-            '.' => Square::Clear,
-            '#' => Square::Tree,
-            _ => panic!(),
+            '.' => Ok(Square::Clear),
+            '#' => Ok(Square::Tree),
+            _ => Err("expected `.` or `#`"),
         }
     }
 }
 
-#[aoc_generator(day3)]
-pub fn parse_input(input: &str) -> Vec<Vec<Square>> {
-    input
-        .lines()
-        .map(|row| row.chars().map(Square::from).collect())
-        .collect()
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Square::Clear => write!(f, "."),
+            Square::Tree => write!(f, "#"),
+        }
+    }
 }
 
-fn navigate(
-    i: usize,
-    j: usize,
-    right_jump: usize,
-    down_jump: usize,
-    max_x_len: usize,
-) -> (usize, usize) {
-    let i = i + down_jump;
-    let j = (j + right_jump) % max_x_len;
-    (i, j)
+#[aoc_generator(day3)]
+pub fn parse_input(input: &str) -> Result<Grid<Square>, ParseError> {
+    Grid::parse(3, input)
 }
 
-pub fn taboggan_trajectory(input: &[Vec<Square>], right_jump: usize, down_jump: usize) -> u32 {
+pub fn taboggan_trajectory(input: &Grid<Square>, right_jump: usize, down_jump: usize) -> u32 {
     let mut counter = 0;
     let mut i = 0;
     let mut j = 0;
     loop {
-        let result = navigate(i, j, right_jump, down_jump, input[0].len());
-        i = result.0;
-        j = result.1;
-        if i >= input.len() {
+        i += down_jump;
+        j += right_jump;
+        if i >= input.height() {
             break;
         }
-        if input[i][j] == Square::Tree {
+        // The map repeats to the right, so wrap around horizontally
+        if *input.get_wrapping(i as isize, j as isize) == Square::Tree {
             counter += 1;
         }
     }
@@ -56,12 +55,12 @@ pub fn taboggan_trajectory(input: &[Vec<Square>], right_jump: usize, down_jump:
 }
 
 #[aoc(day3, part1)]
-pub fn puzzle_1(input: &[Vec<Square>]) -> u32 {
+pub fn puzzle_1(input: &Grid<Square>) -> u32 {
     taboggan_trajectory(input, 3, 1)
 }
 
 #[aoc(day3, part2)]
-pub fn run_scenarios(input: &[Vec<Square>]) -> u64 {
+pub fn run_scenarios(input: &Grid<Square>) -> u64 {
     let input = Arc::new(input.to_owned());
 
     // (right, down)
@@ -89,21 +88,34 @@ pub fn run_scenarios(input: &[Vec<Square>]) -> u64 {
     rx.into_iter().map(|num| num as u64).product()
 }
 
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Grid<Square>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        run_scenarios(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const TEST_INPUT: &str = "..##.........##.........##.........##.........##.........##.......\n#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..#...#...#..\n.#....#..#..#....#..#..#....#..#..#....#..#..#....#..#..#....#..#.\n..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#..#.#...#.#\n.#...##..#..#...##..#..#...##..#..#...##..#..#...##..#..#...##..#.\n..#.##.......#.##.......#.##.......#.##.......#.##.......#.##.....\n.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#.#.#.#....#\n.#........#.#........#.#........#.#........#.#........#.#........#\n#.##...#...#.##...#...#.##...#...#.##...#...#.##...#...#.##...#...\n#...##....##...##....##...##....##...##....##...##....##...##....#\n.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#.#..#...#.#\n";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = parse_input(&TEST_INPUT);
-        assert_eq!(taboggan_trajectory(&input, 3, 1), 7);
-    }
-
-    #[test]
-    fn day_3_puzzle_2_test() {
-        let input = parse_input(&TEST_INPUT);
-        assert_eq!(run_scenarios(&input), 336);
+    aoc_test! {
+        Day3,
+        example: TEST_INPUT => [7, 336],
     }
 }
@@ -1,41 +1,42 @@
+use crate::{parse::ParseError, solution::Solution, Answer};
+
 #[aoc_generator(day4)]
-pub fn generator(input: &str) -> Vec<Vec<(String, String)>> {
-    let (values, _) = input.lines().into_iter().fold(
-        (Vec::default(), false),
-        |(mut v, create_new_entry), line| {
-            let line = line.trim().to_string();
-            if line.is_empty() {
-                return (v, true);
-            }
-            if create_new_entry {
-                v.push(line);
-                return (v, false);
+pub fn generator(input: &str) -> Result<Vec<Vec<(String, String)>>, ParseError> {
+    let mut passports = Vec::new();
+    let mut passport = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        // Passports are separated by blank lines
+        if line.trim().is_empty() {
+            if !passport.is_empty() {
+                passports.push(std::mem::take(&mut passport));
             }
-            if let Some(last) = v.last_mut() {
-                last.push(' ');
-                last.push_str(&line);
-                return (v, false);
+            continue;
+        }
+        for entry in line.split_whitespace() {
+            match entry.split_once(':') {
+                Some((key, value)) if !key.is_empty() => {
+                    passport.push((key.to_string(), value.to_string()))
+                }
+                _ => {
+                    return Err(ParseError::new(
+                        4,
+                        i + 1,
+                        line,
+                        entry,
+                        "expected `<key>:<value>`",
+                    ))
+                }
             }
-            (v, create_new_entry)
-        },
-    );
-    values
-        .into_iter()
-        .map(|v| {
-            v.split(' ')
-                .map(|entry| {
-                    let mut key_value = entry.split(':').map(|v| v.to_string());
-                    let first = key_value.next().unwrap_or_default();
-                    let second = key_value.next().unwrap_or_default();
-                    (first, second)
-                })
-                .collect()
-        })
-        .collect()
+        }
+    }
+    if !passport.is_empty() {
+        passports.push(passport);
+    }
+    Ok(passports)
 }
 
 #[aoc(day4, part1)]
-fn validate_passports(input: &[Vec<(String, String)>]) -> u32 {
+pub fn validate_passports(input: &[Vec<(String, String)>]) -> u32 {
     let count = input
         .iter()
         .filter_map(|passports| match passports.len() {
@@ -48,7 +49,7 @@ fn validate_passports(input: &[Vec<(String, String)>]) -> u32 {
 }
 
 #[aoc(day4, part2)]
-fn validate_passports_2(input: &[Vec<(String, String)>]) -> u32 {
+pub fn validate_passports_2(input: &[Vec<(String, String)>]) -> u32 {
     let count = input
         .iter()
         .filter_map(|passports| {
@@ -75,17 +76,17 @@ fn passport_entry_validator((key, value): &(String, String)) -> bool {
             let mut height = String::new();
             let mut metric = String::new();
             value.chars().for_each(|c| {
-                if c.is_digit(10) {
+                if c.is_ascii_digit() {
                     height.push(c);
                 } else {
                     metric.push(c);
                 }
             });
             if let Ok(height) = height.parse::<u32>() {
-                if &metric == "cm" && height >= 150 && height <= 193 {
+                if &metric == "cm" && (150..=193).contains(&height) {
                     return true;
                 }
-                if &metric == "in" && height >= 59 && height <= 76 {
+                if &metric == "in" && (59..=76).contains(&height) {
                     return true;
                 }
             }
@@ -96,14 +97,14 @@ fn passport_entry_validator((key, value): &(String, String)) -> bool {
             if iter.next() != Some('#') {
                 return false;
             }
-            let count = iter.filter(|c| c.is_digit(16)).count();
+            let count = iter.filter(|c| c.is_ascii_hexdigit()).count();
             if count != 6 {
                 return false;
             }
             true
         }
         "ecl" => ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].contains(&value.as_str()),
-        "pid" => value.chars().filter(|c| c.is_digit(10)).count() == 9,
+        "pid" => value.chars().filter(|c| c.is_ascii_digit()).count() == 9,
         "cid" => true,
         _ => false,
     }
@@ -121,13 +122,31 @@ fn date_validator(date: &str, min: u32, max: u32) -> bool {
     false
 }
 
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<Vec<(String, String)>>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        validate_passports(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        validate_passports_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = r#"
+    const TEST_INPUT: &str = r#"
 ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
 byr:1937 iyr:2017 cid:147 hgt:183cm
 
@@ -142,8 +161,38 @@ hgt:179cm
 hcl:#cfa07d eyr:2025 pid:166559648
 iyr:2011 ecl:brn hgt:59in
             "#;
-        let input = generator(input);
-        let result = validate_passports(&input);
-        assert_eq!(result, 2);
+
+    const INVALID_PASSPORTS: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+    const VALID_PASSPORTS: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+    aoc_test! {
+        Day4,
+        example: TEST_INPUT => [2],
+        invalid_passports: INVALID_PASSPORTS => [4, 0],
+        valid_passports: VALID_PASSPORTS => [4, 4],
     }
 }
@@ -1,3 +1,8 @@
+use crate::{
+    parse::{parse_lines, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
 use std::cmp::Ordering;
 
 #[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
@@ -9,7 +14,7 @@ pub struct Seat {
 
 impl PartialOrd for Seat {
     fn partial_cmp(&self, other: &Seat) -> Option<Ordering> {
-        self.id.partial_cmp(&other.id)
+        Some(self.cmp(other))
     }
 }
 
@@ -20,8 +25,23 @@ impl Ord for Seat {
 }
 
 #[aoc_generator(day5)]
-pub fn generator(input: &str) -> Vec<Seat> {
-    input.lines().map(|l| parse_seat_information(l)).collect()
+pub fn generator(input: &str) -> Result<Vec<Seat>, ParseError> {
+    parse_lines(5, input, |l| {
+        for (i, c) in l.char_indices() {
+            let (valid, expected) = match i {
+                0..=6 => (c == 'F' || c == 'B', "expected `F` or `B`"),
+                7..=9 => (c == 'L' || c == 'R', "expected `L` or `R`"),
+                _ => (false, "expected 10 characters"),
+            };
+            if !valid {
+                return Err(LineError::new(&l[i..i + c.len_utf8()], expected));
+            }
+        }
+        if l.len() != 10 {
+            return Err(LineError::new(l, "expected 10 characters"));
+        }
+        Ok(parse_seat_information(l))
+    })
 }
 
 pub fn parse_seat_information(input: &str) -> Seat {
@@ -78,6 +98,26 @@ pub fn find_missing_seat(input: &[Seat]) -> u32 {
     missing_id
 }
 
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Vec<Seat>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        find_highest_id(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        find_missing_seat(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -92,4 +132,9 @@ mod tests {
         };
         assert_eq!(parse_seat_information(input), expected)
     }
+
+    aoc_test! {
+        Day5,
+        example: "FBFBBFFRLR\nBFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL" => [820],
+    }
 }
@@ -1,10 +1,32 @@
+use crate::{parse::ParseError, solution::Solution, Answer};
+
+/// Blank lines are kept, as they separate each group
 #[aoc_generator(day6)]
-pub fn generator(input: &str) -> Vec<String> {
-    input.lines().map(|s| s.to_string()).collect()
+pub fn generator(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let answers = line.trim();
+            if let Some((j, c)) = answers
+                .char_indices()
+                .find(|(_, c)| !c.is_ascii_lowercase())
+            {
+                return Err(ParseError::new(
+                    6,
+                    i + 1,
+                    line,
+                    &answers[j..j + c.len_utf8()],
+                    "expected a question from `a` to `z`",
+                ));
+            }
+            Ok(answers.to_string())
+        })
+        .collect()
 }
 
 #[aoc(day6, part1)]
-fn solution_1(input: &[String]) -> u32 {
+pub fn solution_1(input: &[String]) -> u32 {
     let mut result = 0;
     let mut buffer = [false; 26];
     for line in input {
@@ -24,7 +46,7 @@ fn solution_1(input: &[String]) -> u32 {
 }
 
 #[aoc(day6, part2)]
-fn solution_2(input: &[String]) -> u32 {
+pub fn solution_2(input: &[String]) -> u32 {
     let mut result = 0;
     let mut buffer = [0u8; 26];
     let mut people_in_group: u8 = 0;
@@ -60,28 +82,32 @@ fn solution_2(input: &[String]) -> u32 {
     result as u32
 }
 
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Vec<String>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        solution_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        solution_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn puzzle_1_works() {
-        let input: Vec<String> = vec![
-            "abc", "", "a", "b", "c", "", "ab", "ac", "", "a", "a", "a", "a", "", "b",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect();
-        assert_eq!(solution_1(&input), 11);
-    }
-    #[test]
-    fn puzzle_2_works() {
-        let input: Vec<String> = vec![
-            "abc", "", "a", "b", "c", "", "ab", "ac", "", "a", "a", "a", "a", "", "b",
-        ]
-        .into_iter()
-        .map(|s| s.to_string())
-        .collect();
-        assert_eq!(solution_2(&input), 6);
+    aoc_test! {
+        Day6,
+        example: "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb" => [11, 6],
     }
 }
@@ -1,5 +1,11 @@
+use crate::{
+    parse::{parse_lines, parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::Deserialize;
 use std::collections::HashMap;
 
 lazy_static! {
@@ -7,7 +13,20 @@ lazy_static! {
         Regex::new(r"(\d+)\s?(\w+\s\w+) bags?").expect("regex should be fine");
 }
 
-const TARGET: &str = "shiny gold";
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The bag type to look for
+    pub target: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            target: "shiny gold".to_string(),
+        }
+    }
+}
 
 /// Bags that contain `no bags` will not be added to this map
 ///
@@ -16,36 +35,44 @@ const TARGET: &str = "shiny gold";
 ///     - `Vec<(i64, String)` - List of nested bag types `String` and the number `(i64)` of bags
 ///     of that type that must be within the parent bag
 #[aoc_generator(day7)]
-pub fn generate_map(input: &str) -> HashMap<String, Vec<(i64, String)>> {
-    let mut map = HashMap::new();
-    for line in input.lines() {
-        let mut split = line.split("bags contain");
-        if let Some(bag_type) = split.next() {
-            if let Some(nested_bags) = split.next() {
-                let iter = REGEX.captures_iter(nested_bags).filter_map(|capture| {
-                    let number_of_bags_required = capture.get(1)?.as_str();
-                    let number_of_bags_required = number_of_bags_required
-                        .parse::<i64>()
-                        .expect("number should be valid number");
-                    let bag_type = capture.get(2)?.as_str();
-                    Some((number_of_bags_required, bag_type.to_string()))
-                });
-
-                let result: Vec<(i64, String)> = iter.collect();
-                // if there are no bags nested in this bag type, don't add it to
-                // the map
-                if result.is_empty() {
-                    continue;
-                }
-                map.insert(bag_type.trim().to_string(), result);
-            }
+pub fn generate_map(input: &str) -> Result<HashMap<String, Vec<(i64, String)>>, ParseError> {
+    let rules = parse_lines(7, input, |line| {
+        let (bag_type, nested_bags) = line
+            .split_once("bags contain")
+            .ok_or_else(|| LineError::new(line, "expected `<bag> bags contain <bags>`"))?;
+        let mut result = Vec::new();
+        for capture in REGEX.captures_iter(nested_bags) {
+            let number_of_bags_required = capture.get(1).expect("group is not optional");
+            let bag_type = capture.get(2).expect("group is not optional");
+            result.push((
+                parse_number(number_of_bags_required.as_str())?,
+                bag_type.as_str().to_string(),
+            ));
         }
-    }
-    map
+        if result.is_empty() && !nested_bags.trim().starts_with("no other bags") {
+            return Err(LineError::new(
+                nested_bags.trim(),
+                "expected a list of bags or `no other bags`",
+            ));
+        }
+        Ok((bag_type.trim().to_string(), result))
+    })?;
+
+    // if there are no bags nested in this bag type, don't add it to
+    // the map
+    Ok(rules
+        .into_iter()
+        .filter(|(_, nested_bags)| !nested_bags.is_empty())
+        .collect())
 }
 
 #[aoc(day7, part1)]
 pub fn solution(map: &HashMap<String, Vec<(i64, String)>>) -> i32 {
+    bags_containing(map, &Config::default().target)
+}
+
+/// How many bag types can eventually contain a `target` bag
+pub fn bags_containing(map: &HashMap<String, Vec<(i64, String)>>, target: &str) -> i32 {
     // (i64, bool)
     // `i64` - stores the total number of bags stored within that bag
     // `bool` - stores whether it's possible to store the target bag somewhere
@@ -56,7 +83,7 @@ pub fn solution(map: &HashMap<String, Vec<(i64, String)>>) -> i32 {
     // is finished, we can easily just do a look up to find out how many bags the target bag contains
     map.keys()
         .filter_map(|key| {
-            if recurse_into_bag(&key, &map, &mut cache, false).1 {
+            if recurse_into_bag(key, target, map, &mut cache, false).1 {
                 return Some(());
             }
             None
@@ -66,12 +93,18 @@ pub fn solution(map: &HashMap<String, Vec<(i64, String)>>) -> i32 {
 
 #[aoc(day7, part2)]
 pub fn solution_2(map: &HashMap<String, Vec<(i64, String)>>) -> i64 {
+    bags_inside(map, &Config::default().target)
+}
+
+/// How many bags are required inside a single `target` bag
+pub fn bags_inside(map: &HashMap<String, Vec<(i64, String)>>, target: &str) -> i64 {
     let mut cache = HashMap::new();
-    recurse_into_bag(TARGET, &map, &mut cache, true).0
+    recurse_into_bag(target, target, map, &mut cache, true).0
 }
 
 fn recurse_into_bag(
     key: &str,
+    target: &str,
     map: &HashMap<String, Vec<(i64, String)>>,
     cache: &mut HashMap<String, (i64, bool)>,
     solution_2: bool,
@@ -87,14 +120,14 @@ fn recurse_into_bag(
     // other bags within it
     if let Some(child_keys) = map.get(key) {
         for (multiplier, child_key) in child_keys {
-            let (count, can_store) = recurse_into_bag(child_key, map, cache, solution_2);
+            let (count, can_store) = recurse_into_bag(child_key, target, map, cache, solution_2);
 
             total_count += (multiplier * count) + multiplier;
 
             // If you wanted to specialise for puzzle one, you could break here
             // as if one of the child bags can store the target bag, you don't
             // need to check the rest of the children
-            if can_store || child_key == TARGET {
+            if can_store || child_key == target {
                 can_store_target_bag = true;
                 if !solution_2 {
                     break;
@@ -109,6 +142,26 @@ fn recurse_into_bag(
     result
 }
 
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = HashMap<String, Vec<(i64, String)>>;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generate_map(input)
+    }
+
+    fn part_1(input: &Self::Input, config: &Config) -> Answer {
+        bags_containing(input, &config.target).into()
+    }
+
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        bags_inside(input, &config.target).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,15 +184,10 @@ mod tests {
         dark blue bags contain 2 dark violet bags.\n
         dark violet bags contain no other bags.";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generate_map(TEST_INPUT_1);
-        assert_eq!(solution(&input), 4);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generate_map(TEST_INPUT_2);
-        assert_eq!(solution_2(&input), 126);
+    aoc_test! {
+        Day7,
+        example: TEST_INPUT_1 => [4, 32],
+        example_2: TEST_INPUT_2 => [_, 126],
+        different_target: TEST_INPUT_1, Config { target: "faded blue".to_string() } => [7, 0],
     }
 }
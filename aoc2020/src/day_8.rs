@@ -1,3 +1,10 @@
+use crate::{
+    metrics,
+    parse::{parse_lines, parse_number, LineError, ParseError},
+    solution::Solution,
+    Answer,
+};
+
 #[derive(Debug, Clone, Copy)]
 pub enum Operation {
     Nop(isize),
@@ -6,24 +13,39 @@ pub enum Operation {
 }
 
 impl Operation {
-    pub fn new(input: &str) -> Option<Operation> {
+    pub fn new(input: &str) -> Result<Operation, LineError<'_>> {
         let mut iter = input.split_whitespace();
-        let op = iter.next()?;
-        let val = iter.next()?;
-        let val: isize = val.parse().ok()?;
+        let op = iter
+            .next()
+            .ok_or_else(|| LineError::new(input, "expected an operation"))?;
+        let val = iter
+            .next()
+            .ok_or_else(|| LineError::new(input, "expected an argument"))?;
+        let val: isize = parse_number(val)?;
+        if let Some(extra) = iter.next() {
+            return Err(LineError::new(extra, "unexpected input after the argument"));
+        }
 
-        Some(match op {
+        Ok(match op {
             "nop" => Operation::Nop(val),
             "jmp" => Operation::Jmp(val),
             "acc" => Operation::Acc(val),
-            _ => panic!("invalid operation"),
+            _ => return Err(LineError::new(op, "expected `nop`, `acc` or `jmp`")),
         })
     }
 }
 
 #[aoc_generator(day8)]
-pub fn generator(input: &str) -> Vec<Operation> {
-    input.lines().filter_map(|l| Operation::new(l)).collect()
+pub fn generator(input: &str) -> Result<Vec<Operation>, ParseError> {
+    let program = parse_lines(8, input, Operation::new)?;
+    if program.is_empty() {
+        return Err(ParseError::unexpected_end(
+            8,
+            input.lines().count() + 1,
+            "expected an instruction",
+        ));
+    }
+    Ok(program)
 }
 
 #[aoc(day8, part1)]
@@ -36,6 +58,7 @@ pub fn puzzle_1(input: &[Operation]) -> i32 {
             return acc;
         }
         freq[ptr] = true;
+        metrics::count("instructions", 1);
         match input[ptr] {
             Operation::Nop(_) => {
                 ptr += 1;
@@ -83,6 +106,7 @@ pub fn run_scenario(input: &[Operation], mut ptr: usize, mut acc: i32) -> Option
     let mut freq = vec![false; input.len()];
     loop {
         freq[ptr] = true;
+        metrics::count("instructions", 1);
         let results = match_op(input, ptr, acc);
         ptr = results.0;
         acc = results.1;
@@ -106,6 +130,26 @@ fn match_op(input: &[Operation], ptr: usize, acc: i32) -> (usize, i32) {
     }
 }
 
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Vec<Operation>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {
+        puzzle_1(input).into()
+    }
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {
+        puzzle_2(input).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,14 +167,21 @@ acc +6\n
         ";
 
     #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 5);
+    fn generator_reports_unknown_operation() {
+        let error = generator("nop +0\nmul +3\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.text, "mul");
     }
 
     #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 8);
+    fn generator_rejects_an_empty_program() {
+        let error = generator("\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(error.text.is_empty());
+    }
+
+    aoc_test! {
+        Day8,
+        example: TEST_INPUT => [5, 8],
     }
 }
@@ -1,31 +1,41 @@
+use crate::{
+    parse::{parse_lines, parse_number, ParseError},
+    solution::Solution,
+    Answer,
+};
+use serde::Deserialize;
 use std::collections::{HashSet, VecDeque};
 
-#[cfg(not(test))]
-const PREAMBLE: usize = 25;
-
-#[cfg(test)]
-const PREAMBLE: usize = 5;
-
-#[cfg(not(test))]
-const WEAKNESS: i32 = 258585477;
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// How many previous numbers the next one has to be a sum of two of
+    pub preamble: usize,
+}
 
-#[cfg(test)]
-const WEAKNESS: i32 = 127;
+impl Default for Config {
+    fn default() -> Self {
+        Self { preamble: 25 }
+    }
+}
 
 #[aoc_generator(day9)]
-pub fn generator(input: &str) -> Vec<i32> {
-    input
-        .lines()
-        .filter_map(|l| l.parse::<i32>().ok())
-        .collect()
+pub fn generator(input: &str) -> Result<Vec<i64>, ParseError> {
+    parse_lines(9, input, parse_number)
 }
 
 #[aoc(day9, part1)]
-pub fn puzzle_1(input: &[i32]) -> i32 {
-    let mut buffer = VecDeque::with_capacity(PREAMBLE);
-    let mut set = HashSet::with_capacity(PREAMBLE);
+pub fn puzzle_1(input: &[i64]) -> i64 {
+    find_weakness(input, Config::default().preamble)
+}
+
+/// The first number which isn't the sum of two of the `preamble` numbers
+/// before it
+pub fn find_weakness(input: &[i64], preamble: usize) -> i64 {
+    let mut buffer = VecDeque::with_capacity(preamble);
+    let mut set = HashSet::with_capacity(preamble);
     'outer: for (i, num) in input.iter().enumerate() {
-        if i < PREAMBLE {
+        if i < preamble {
             buffer.push_front(num);
             set.insert(num);
             continue;
@@ -50,27 +60,34 @@ pub fn puzzle_1(input: &[i32]) -> i32 {
 }
 
 #[aoc(day9, part2)]
-pub fn puzzle_2(input: &[i32]) -> i32 {
+pub fn puzzle_2(input: &[i64]) -> i64 {
+    break_encryption(input, Config::default().preamble)
+}
+
+/// The sum of the smallest and largest numbers of the contiguous run which
+/// adds up to the weakness found by [`find_weakness`]
+pub fn break_encryption(input: &[i64], preamble: usize) -> i64 {
+    let weakness = find_weakness(input, preamble);
     let mut left_ptr = 0;
     let mut right_ptr = 1;
     let mut current_total = input[0] + input[1];
 
     while right_ptr < input.len() {
-        while current_total < WEAKNESS {
+        while current_total < weakness {
             right_ptr += 1;
             current_total += input[right_ptr];
         }
-        while current_total > WEAKNESS {
+        while current_total > weakness {
             current_total -= input[left_ptr];
             left_ptr += 1;
         }
-        if current_total == WEAKNESS {
+        if current_total == weakness {
             break;
         }
     }
 
-    let mut max = std::i32::MIN;
-    let mut min = std::i32::MAX;
+    let mut max = i64::MIN;
+    let mut min = i64::MAX;
 
     while left_ptr <= right_ptr {
         max = max.max(input[left_ptr]);
@@ -81,6 +98,26 @@ pub fn puzzle_2(input: &[i32]) -> i32 {
     max + min
 }
 
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<i64>;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part_1(input: &Self::Input, config: &Config) -> Answer {
+        find_weakness(input, config.preamble).into()
+    }
+
+    fn part_2(input: &Self::Input, config: &Config) -> Answer {
+        break_encryption(input, config.preamble).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,15 +144,8 @@ mod tests {
 309
 576";
 
-    #[test]
-    fn puzzle_1_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_1(&input), 127);
-    }
-
-    #[test]
-    fn puzzle_2_test() {
-        let input = generator(TEST_INPUT);
-        assert_eq!(puzzle_2(&input), 62);
+    aoc_test! {
+        Day9,
+        example: TEST_INPUT, Config { preamble: 5 } => [127, 62],
     }
 }
@@ -0,0 +1,103 @@
+use super::Rng;
+use std::collections::HashSet;
+
+const TARGET_SUM: usize = 2020;
+
+/// An expense report of `size` entries, at least 5, with exactly one pair and
+/// one triple which sum to 2020
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let special = special_entries(rng);
+    let mut used: HashSet<usize> = special.iter().copied().collect();
+
+    // Any two fillers sum to more than 2020, so they only have to avoid
+    // completing a pair or triple with the special entries
+    let mut forbidden = used.clone();
+    for (i, a) in special.iter().enumerate() {
+        forbidden.insert(TARGET_SUM - a);
+        for b in &special[i + 1..] {
+            if a + b < TARGET_SUM {
+                forbidden.insert(TARGET_SUM - a - b);
+            }
+        }
+    }
+    let available = (TARGET_SUM / 2 + 1..=TARGET_SUM)
+        .filter(|n| !forbidden.contains(n))
+        .count();
+
+    let mut entries = special.to_vec();
+    while entries.len() < size.max(special.len()).min(special.len() + available) {
+        let n = rng.range(TARGET_SUM / 2 + 1..=TARGET_SUM);
+        if !forbidden.contains(&n) && used.insert(n) {
+            entries.push(n);
+        }
+    }
+    rng.shuffle(&mut entries);
+    entries
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `[a, 2020 - a, b, c, d]` where `b + c + d == 2020`
+fn special_entries(rng: &mut Rng) -> [usize; 5] {
+    loop {
+        let a = rng.range(200..=1000);
+        let b = rng.range(1..=150);
+        let c = rng.range(b + 1..=(TARGET_SUM - b) / 2);
+        let entries = [a, TARGET_SUM - a, b, c, TARGET_SUM - b - c];
+        if is_unique(&entries) {
+            return entries;
+        }
+    }
+}
+
+/// Checks the only pair and triple summing to 2020 are the intended ones, and
+/// that no entry is repeated, or is half of what's left after taking `b`
+fn is_unique(entries: &[usize; 5]) -> bool {
+    let distinct: HashSet<_> = entries.iter().collect();
+    if distinct.len() != entries.len() || entries[2..].iter().any(|e| e * 2 == TARGET_SUM) {
+        return false;
+    }
+    let b = entries[2];
+    if entries.iter().any(|e| *e * 2 == TARGET_SUM - b) {
+        return false;
+    }
+    let mut pairs = 0;
+    let mut triples = 0;
+    for i in 0..entries.len() {
+        for j in i + 1..entries.len() {
+            if entries[i] + entries[j] == TARGET_SUM {
+                pairs += 1;
+            }
+            for k in j + 1..entries.len() {
+                if entries[i] + entries[j] + entries[k] == TARGET_SUM {
+                    triples += 1;
+                }
+            }
+        }
+    }
+    pairs == 1 && triples == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_1::generator;
+
+    #[test]
+    fn pair_and_triple_are_unique() {
+        for seed in 0..10 {
+            let input = generator(&generate(&mut Rng::new(seed), 100)).unwrap();
+            assert_eq!(input.len(), 100);
+            let (mut pairs, mut triples) = (0, 0);
+            for (i, a) in input.iter().enumerate() {
+                for (j, b) in input.iter().enumerate().skip(i + 1) {
+                    pairs += (a + b == 2020) as usize;
+                    triples += input[j + 1..].iter().filter(|c| a + b + *c == 2020).count();
+                }
+            }
+            assert_eq!((pairs, triples), (1, 1), "seed {}", seed);
+        }
+    }
+}
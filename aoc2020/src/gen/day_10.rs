@@ -0,0 +1,37 @@
+use super::Rng;
+
+/// Arrangements of a run of 1 to 5 adapters which are 1 jolt apart, where the
+/// first and last adapters of the run have to be used
+const ARRANGEMENTS: [u64; 5] = [1, 1, 2, 4, 7];
+
+/// Stop adding runs of adapters once this many arrangements are possible, so
+/// part 2's answer fits in a `u64`
+const MAX_ARRANGEMENTS: u64 = 1 << 56;
+
+/// `size` adapters, each within 1 or 3 jolts of the next, as in the real input
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut adapters = Vec::with_capacity(size);
+    let mut arrangements = 1;
+    // The outlet starts the first run
+    let mut joltage = 0;
+    while adapters.len() < size {
+        let mut run = rng.range(0..=ARRANGEMENTS.len() - 1);
+        if arrangements * ARRANGEMENTS[run] > MAX_ARRANGEMENTS {
+            run = 0;
+        }
+        arrangements *= ARRANGEMENTS[run];
+        for _ in 0..run {
+            joltage += 1;
+            adapters.push(joltage);
+        }
+        joltage += 3;
+        adapters.push(joltage);
+    }
+    adapters.truncate(size);
+    rng.shuffle(&mut adapters);
+    adapters
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
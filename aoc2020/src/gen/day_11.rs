@@ -0,0 +1,14 @@
+use super::Rng;
+
+/// A waiting area `size` seats square, where most positions are seats
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.75) { 'L' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,14 @@
+use super::Rng;
+
+/// `size` navigation instructions, with values in the same ranges as the real
+/// input so the ship and waypoint stay close enough to fit in an `i32`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| match rng.range(0..=6) {
+            0..=3 => format!("{}{}", rng.choose(&['N', 'S', 'E', 'W']), rng.range(1..=5)),
+            4 => format!("{}{}", rng.choose(&['L', 'R']), rng.range(1..=3) * 90),
+            _ => format!("F{}", rng.range(1..=100)),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
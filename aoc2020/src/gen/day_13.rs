@@ -0,0 +1,35 @@
+use super::Rng;
+
+/// Keeps the timestamp found by part 2 well within a `u64`
+const MAX_PRODUCT: u64 = 1 << 50;
+
+/// A schedule with `size` slots, at least 1, where about one in six is a bus
+///
+/// The bus ids are distinct primes, as part 2 relies on them being pairwise
+/// coprime
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let slots = size.max(1);
+    let mut primes = primes_between(7, 1000);
+    rng.shuffle(&mut primes);
+
+    let mut buses = Vec::new();
+    let mut product = 1;
+    for prime in primes.into_iter().take((slots / 6).max(1)) {
+        if product * prime > MAX_PRODUCT {
+            break;
+        }
+        product *= prime;
+        buses.push(prime.to_string());
+    }
+    buses.resize(slots, "x".to_string());
+    // The first slot is always a bus, as in the real input
+    rng.shuffle(&mut buses[1..]);
+
+    format!("{}\n{}", rng.range(100_000..=1_000_000), buses.join(","))
+}
+
+fn primes_between(low: u64, high: u64) -> Vec<u64> {
+    (low..=high)
+        .filter(|n| (2..).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect()
+}
@@ -0,0 +1,29 @@
+use super::Rng;
+
+/// `size` masks, each followed by a few writes
+///
+/// Masks have between 2 and 8 floating bits, part 2 expects at least 2 and
+/// tries every permutation of them
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut lines = Vec::new();
+    for _ in 0..size.max(1) {
+        let mut mask: Vec<char> = (0..36)
+            .map(|_| if rng.chance(0.5) { '1' } else { '0' })
+            .collect();
+        let mut bits: Vec<usize> = (0..mask.len()).collect();
+        rng.shuffle(&mut bits);
+        for bit in bits.iter().take(rng.range(2..=8)) {
+            mask[*bit] = 'X';
+        }
+        lines.push(format!("mask = {}", mask.iter().collect::<String>()));
+
+        for _ in 0..rng.range(1..=5) {
+            lines.push(format!(
+                "mem[{}] = {}",
+                rng.range(0..=0xffff),
+                rng.range(0..=1 << 30)
+            ));
+        }
+    }
+    lines.join("\n")
+}
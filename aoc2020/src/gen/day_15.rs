@@ -0,0 +1,14 @@
+use super::Rng;
+
+/// `size` distinct starting numbers, at least 1
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut numbers: Vec<usize> = (0..=size.max(10) * 2).collect();
+    rng.shuffle(&mut numbers);
+    numbers
+        .iter()
+        .take(size)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,148 @@
+use super::Rng;
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// Part 1 only keeps track of values below this
+const MAX_VALUE: usize = 999;
+
+/// `(low, low end, high start, high)` for the rule `low-low end or high start-high`
+type Rule = (usize, usize, usize, usize);
+
+/// Your ticket and `size` nearby tickets, at least 1, about a fifth of which
+/// have an invalid value
+///
+/// The fields are solved in a random order, each field's rule accepts a subset
+/// of the values of the field solved before it. Each column has a value which
+/// only its field and the fields before it accept, so once the fields before
+/// it are assigned there's only one field left for it. This gives a unique
+/// assignment, which part 2 finds by repeatedly taking the columns with a
+/// single candidate
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut fields = FIELDS.to_vec();
+    rng.shuffle(&mut fields);
+    let rules = nested_rules(rng, fields.len());
+    // `columns[i]` is the column of the field `fields[i]`
+    let mut columns: Vec<usize> = (0..fields.len()).collect();
+    rng.shuffle(&mut columns);
+
+    let ticket = |rng: &mut Rng| {
+        let mut ticket = vec![0; fields.len()];
+        for (rule, column) in rules.iter().zip(&columns) {
+            ticket[*column] = accepted_value(rng, *rule);
+        }
+        ticket
+    };
+
+    let your_ticket = ticket(rng);
+    let tickets = size.max(1);
+    let valid = tickets - tickets / 5;
+    let mut nearby: Vec<Vec<usize>> = (0..tickets).map(|_| ticket(rng)).collect();
+    // Every column needs a value on a valid ticket ruling out the fields
+    // solved after it
+    for (i, column) in columns.iter().enumerate().take(fields.len() - 1) {
+        let ticket = rng.range(0..=valid - 1);
+        nearby[ticket][*column] = distinguishing_value(rng, rules[i], rules[i + 1]);
+    }
+    for ticket in nearby.iter_mut().skip(valid) {
+        let column = rng.range(0..=fields.len() - 1);
+        ticket[column] = invalid_value(rng, rules[0]);
+    }
+    rng.shuffle(&mut nearby);
+
+    let mut sections: Vec<(&str, Rule)> = fields.iter().copied().zip(rules).collect();
+    rng.shuffle(&mut sections);
+    let rules: Vec<String> = sections
+        .iter()
+        .map(|(field, (a, b, c, d))| format!("{}: {}-{} or {}-{}", field, a, b, c, d))
+        .collect();
+    let tickets: Vec<String> = nearby.iter().map(|t| join(t)).collect();
+    format!(
+        "{}\n\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        rules.join("\n"),
+        join(&your_ticket),
+        tickets.join("\n")
+    )
+}
+
+/// Each rule is strictly inside the one before, with one of its bounds moved in
+fn nested_rules(rng: &mut Rng, count: usize) -> Vec<Rule> {
+    let mut rule = (
+        rng.range(25..=50),
+        rng.range(400..=450),
+        rng.range(500..=550),
+        rng.range(900..=970),
+    );
+    let mut rules = vec![rule];
+    for _ in 1..count {
+        let step = rng.range(1..=10);
+        match rng.range(0..=3) {
+            0 => rule.0 += step,
+            1 => rule.1 -= step,
+            2 => rule.2 += step,
+            _ => rule.3 -= step,
+        }
+        rules.push(rule);
+    }
+    rules
+}
+
+fn accepted_value(rng: &mut Rng, (a, b, c, d): Rule) -> usize {
+    if rng.chance(0.5) {
+        rng.range(a..=b)
+    } else {
+        rng.range(c..=d)
+    }
+}
+
+/// A value accepted by `rule` but not by `next`, which is nested inside it
+fn distinguishing_value(rng: &mut Rng, rule: Rule, next: Rule) -> usize {
+    loop {
+        let value = accepted_value(rng, rule);
+        if !accepts(next, value) {
+            return value;
+        }
+    }
+}
+
+/// A value which isn't accepted by any rule, as they're all inside `outer`
+fn invalid_value(rng: &mut Rng, outer: Rule) -> usize {
+    loop {
+        let value = rng.range(1..=MAX_VALUE);
+        if !accepts(outer, value) {
+            return value;
+        }
+    }
+}
+
+fn accepts((a, b, c, d): Rule, value: usize) -> bool {
+    (a..=b).contains(&value) || (c..=d).contains(&value)
+}
+
+fn join(ticket: &[usize]) -> String {
+    ticket
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
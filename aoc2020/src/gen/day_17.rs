@@ -0,0 +1,14 @@
+use super::Rng;
+
+/// An initial slice `size` cubes square, about half of which are active
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.5) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
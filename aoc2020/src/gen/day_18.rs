@@ -0,0 +1,40 @@
+use super::Rng;
+
+/// `size` expressions of between 2 and 12 single digit numbers
+///
+/// An expression of `n` digits evaluates to less than `10^n` under either set
+/// of rules, so the sum of the answers stays well within a `u64`
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let digits = rng.range(2..=12);
+            expression(rng, digits)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// An expression of `digits` numbers, where runs of more than one number are
+/// wrapped in parens
+fn expression(rng: &mut Rng, digits: usize) -> String {
+    let terms = rng.range(2..=digits.min(5));
+    // Split the digits into `terms` non empty groups
+    let mut cuts: Vec<usize> = (1..digits).collect();
+    rng.shuffle(&mut cuts);
+    cuts.truncate(terms - 1);
+    cuts.sort_unstable();
+    cuts.insert(0, 0);
+    cuts.push(digits);
+
+    let mut output = String::new();
+    for (i, group) in cuts.windows(2).enumerate() {
+        if i > 0 {
+            output.push_str(if rng.chance(0.5) { " + " } else { " * " });
+        }
+        match group[1] - group[0] {
+            1 => output.push((b'1' + rng.range(0..=8) as u8) as char),
+            n => output.push_str(&format!("({})", expression(rng, n))),
+        }
+    }
+    output
+}
@@ -0,0 +1,139 @@
+use super::Rng;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Rule numbers with a fixed meaning, part 2 replaces rules 8 and 11 with
+/// looping versions of themselves
+const RESERVED: [usize; 5] = [0, 8, 11, 31, 42];
+
+/// Rules in the same shape as the real input, followed by `size` messages
+///
+/// Rules 42 and 31 each match a random set of strings of 3 or 4 letters, and
+/// rule 0 matches `42 42 31`. Part 1 lists every message rule 0 matches, so
+/// these strings are kept much shorter than the real input's
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let length = rng.range(3..=4);
+    let (forty_two, thirty_one) = split_strings(rng, length);
+
+    let mut numbers: Vec<usize> = (1..=130).filter(|n| !RESERVED.contains(n)).collect();
+    rng.shuffle(&mut numbers);
+    let mut grammar = Grammar {
+        numbers,
+        rules: BTreeMap::new(),
+        defined: HashMap::new(),
+    };
+    let rule_42 = grammar.body(&forty_two);
+    let rule_31 = grammar.body(&thirty_one);
+    let mut rules = grammar.rules;
+    rules.insert(0, "8 11".to_string());
+    rules.insert(8, "42".to_string());
+    rules.insert(11, "42 31".to_string());
+    rules.insert(42, rule_42);
+    rules.insert(31, rule_31);
+
+    let mut rules: Vec<String> = rules
+        .into_iter()
+        .map(|(number, rule)| format!("{}: {}", number, rule))
+        .collect();
+    rng.shuffle(&mut rules);
+
+    let forty_two: Vec<_> = forty_two.into_iter().collect();
+    let thirty_one: Vec<_> = thirty_one.into_iter().collect();
+    let messages: Vec<String> = (0..size)
+        .map(|_| {
+            let (leading, trailing) = match rng.range(0..=3) {
+                // Matches rule 0 before and after the loops
+                0 => (2, 1),
+                // Only matches once rules 8 and 11 loop
+                1 => {
+                    let trailing = rng.range(1..=3);
+                    (trailing + rng.range(1..=3), trailing)
+                }
+                // Too many 31s to match either way
+                2 => {
+                    let leading = rng.range(1..=3);
+                    (leading, leading + rng.range(0..=2))
+                }
+                // Random letters
+                _ => {
+                    return (0..length * rng.range(2..=6))
+                        .map(|_| if rng.chance(0.5) { 'a' } else { 'b' })
+                        .collect();
+                }
+            };
+            let mut message = String::new();
+            for _ in 0..leading {
+                message.push_str(rng.choose(&forty_two).as_str());
+            }
+            for _ in 0..trailing {
+                message.push_str(rng.choose(&thirty_one).as_str());
+            }
+            message
+        })
+        .collect();
+    format!("{}\n\n{}", rules.join("\n"), messages.join("\n"))
+}
+
+/// Splits every string of `a`s and `b`s of `length` letters into two non empty
+/// sets
+fn split_strings(rng: &mut Rng, length: usize) -> (BTreeSet<String>, BTreeSet<String>) {
+    loop {
+        let (left, right): (BTreeSet<String>, BTreeSet<String>) = (0..1 << length)
+            .map(|bits: usize| {
+                (0..length)
+                    .map(|i| if bits & (1 << i) == 0 { 'a' } else { 'b' })
+                    .collect::<String>()
+            })
+            .partition(|_| rng.chance(0.5));
+        if !left.is_empty() && !right.is_empty() {
+            return (left, right);
+        }
+    }
+}
+
+/// Builds rules matching sets of strings of the same length, rules are shared
+/// between sets of strings which are the same
+struct Grammar {
+    /// Unused rule numbers
+    numbers: Vec<usize>,
+    rules: BTreeMap<usize, String>,
+    defined: HashMap<BTreeSet<String>, usize>,
+}
+
+impl Grammar {
+    /// The number of a rule matching exactly `strings`
+    fn rule(&mut self, strings: &BTreeSet<String>) -> usize {
+        if let Some(number) = self.defined.get(strings) {
+            return *number;
+        }
+        let body = self.body(strings);
+        let number = self.numbers.pop().expect("ran out of rule numbers");
+        self.rules.insert(number, body);
+        self.defined.insert(strings.clone(), number);
+        number
+    }
+
+    /// A rule which matches exactly `strings`, split on their first letter
+    fn body(&mut self, strings: &BTreeSet<String>) -> String {
+        if strings.len() == 1 && strings.iter().all(|s| s.len() == 1) {
+            return format!("{:?}", strings.iter().next().expect("checked length"));
+        }
+        let mut alternatives = Vec::new();
+        for letter in &["a", "b"] {
+            let rest: BTreeSet<String> = strings
+                .iter()
+                .filter_map(|s| s.strip_prefix(letter))
+                .map(str::to_string)
+                .collect();
+            if rest.is_empty() {
+                continue;
+            }
+            let letter_rule = self.rule(&std::iter::once(letter.to_string()).collect());
+            if rest.iter().all(String::is_empty) {
+                alternatives.push(letter_rule.to_string());
+            } else {
+                alternatives.push(format!("{} {}", letter_rule, self.rule(&rest)));
+            }
+        }
+        alternatives.join(" | ")
+    }
+}
@@ -0,0 +1,29 @@
+use super::Rng;
+
+/// `size` password policies, about half of which are valid under each policy
+///
+/// Both numbers in a policy are always positions within the password, as part
+/// 2 indexes the password with them
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let length = rng.range(3..=20);
+            let password: String = (0..length).map(|_| letter(rng)).collect();
+            let min = rng.range(1..=length);
+            let max = rng.range(min..=length);
+            // Mostly pick a letter from the password, so that some are valid
+            let letter = if rng.chance(0.8) {
+                *rng.choose(&password.chars().collect::<Vec<_>>())
+            } else {
+                letter(rng)
+            };
+            format!("{}-{} {}: {}", min, max, letter, password)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A letter from a small alphabet, so they're repeated often
+fn letter(rng: &mut Rng) -> char {
+    (b'a' + rng.range(0..=5) as u8) as char
+}
@@ -0,0 +1,166 @@
+use super::Rng;
+use crate::grid::{Grid, Point};
+use std::collections::HashSet;
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// Tiles are 10 pixels square, and share their borders with their neighbours
+const TILE: usize = 10;
+
+/// An image `size` tiles square, between 2 and 12, cut into tiles which are
+/// then shuffled, rotated and flipped
+///
+/// The image is random apart from a few sea monsters, and every border
+/// between two tiles is unique and not a palindrome, so each tile only lines
+/// up with its real neighbours. There aren't enough distinct borders for much
+/// larger images
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let tiles = size.clamp(2, 12);
+    let image = image(rng, tiles * (TILE - 2));
+
+    let side = tiles * (TILE - 1) + 1;
+    let mut photo = Grid::filled(side, side, '.');
+    for ((row, col), pixel) in image.points() {
+        let to_photo = |n: usize| n / (TILE - 2) * (TILE - 1) + 1 + n % (TILE - 2);
+        photo[(to_photo(row), to_photo(col))] = *pixel;
+    }
+    while !draw_borders(rng, &mut photo, tiles) {}
+
+    let mut ids: Vec<usize> = (1000..=9999).collect();
+    rng.shuffle(&mut ids);
+    let mut cut: Vec<String> = (0..tiles * tiles)
+        .map(|i| {
+            let (top, left) = (i / tiles * (TILE - 1), i % tiles * (TILE - 1));
+            let cells = (0..TILE * TILE)
+                .map(|j| photo[(top + j / TILE, left + j % TILE)])
+                .collect();
+            let mut tile = Grid::new(TILE, TILE, cells);
+            for _ in 0..rng.range(0..=3) {
+                tile.rotate_clockwise();
+            }
+            if rng.chance(0.5) {
+                tile.flip_horizontal();
+            }
+            format!("Tile {}:\n{}", ids[i], tile)
+        })
+        .collect();
+    rng.shuffle(&mut cut);
+    cut.join("\n\n")
+}
+
+/// A random image `side` pixels square with some sea monsters hidden in it,
+/// if they fit
+fn image(rng: &mut Rng, side: usize) -> Grid<char> {
+    let mut image = Grid::new(
+        side,
+        side,
+        (0..side * side)
+            .map(|_| if rng.chance(0.4) { '#' } else { '.' })
+            .collect(),
+    );
+    let (height, width) = (SEA_MONSTER.len(), SEA_MONSTER[0].len());
+    if side < width {
+        return image;
+    }
+    let mut taken: Vec<Point> = Vec::new();
+    for _ in 0..rng.range(1..=side * side / 200 + 1) {
+        let top = rng.range(0..=side - height);
+        let left = rng.range(0..=side - width);
+        // Monsters don't overlap
+        if taken.iter().any(|(t, l)| {
+            top < t + height && *t < top + height && left < l + width && *l < left + width
+        }) {
+            continue;
+        }
+        taken.push((top, left));
+        for (row, line) in SEA_MONSTER.iter().enumerate() {
+            for (col, c) in line.char_indices() {
+                if c == '#' {
+                    image[(top + row, left + col)] = '#';
+                }
+            }
+        }
+    }
+    image
+}
+
+/// Fills in the borders between every tile, returns `false` if it ran out of
+/// unique borders and should be tried again
+fn draw_borders(rng: &mut Rng, photo: &mut Grid<char>, tiles: usize) -> bool {
+    let pixel = |rng: &mut Rng| if rng.chance(0.5) { '#' } else { '.' };
+    let step = TILE - 1;
+    for row in 0..=tiles {
+        for col in 0..=tiles {
+            photo[(row * step, col * step)] = pixel(rng);
+        }
+    }
+
+    let mut borders: Vec<Vec<Point>> = Vec::new();
+    for a in 0..=tiles {
+        for b in 0..tiles {
+            borders.push((0..TILE).map(|i| (a * step, b * step + i)).collect());
+            borders.push((0..TILE).map(|i| (b * step + i, a * step)).collect());
+        }
+    }
+
+    let mut used = HashSet::new();
+    for border in borders {
+        let inner = TILE - 2;
+        let start = rng.range(0..=(1 << inner) - 1);
+        let pixels = (0..1 << inner)
+            .map(|k| (start + k) % (1 << inner))
+            .find_map(|bits| {
+                let pixels: Vec<char> = border
+                    .iter()
+                    .enumerate()
+                    .map(|(i, point)| match i {
+                        _ if i == 0 || i == TILE - 1 => photo[*point],
+                        _ if bits & (1 << (i - 1)) != 0 => '#',
+                        _ => '.',
+                    })
+                    .collect();
+                let reversed: Vec<char> = pixels.iter().rev().copied().collect();
+                if pixels == reversed || used.contains(&pixels) {
+                    return None;
+                }
+                Some((pixels, reversed))
+            });
+        let (pixels, reversed) = match pixels {
+            Some(pixels) => pixels,
+            None => return false,
+        };
+        for (point, pixel) in border.iter().zip(&pixels) {
+            photo[*point] = *pixel;
+        }
+        used.insert(pixels);
+        used.insert(reversed);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_20::generator;
+    use std::collections::HashMap;
+
+    #[test]
+    fn borders_only_match_neighbours() {
+        for tiles in 2..=4 {
+            let photos = generator(&generate(&mut Rng::new(tiles as u64), tiles)).unwrap();
+            assert_eq!(photos.len(), tiles * tiles);
+            let mut matches: HashMap<Vec<char>, usize> = HashMap::new();
+            for edge in photos.values().flat_map(|p| p.edges()) {
+                let reversed: Vec<char> = edge.iter().rev().copied().collect();
+                *matches.entry(edge.min(reversed)).or_default() += 1;
+            }
+            let shared = matches.values().filter(|n| **n == 2).count();
+            assert_eq!(shared, 2 * tiles * (tiles - 1));
+            assert_eq!(matches.len(), shared + 4 * tiles);
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use super::Rng;
+
+const WIDTH: usize = 31;
+
+/// A map `size` rows tall and 31 columns wide, as the real input is
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            (0..WIDTH)
+                .map(|_| if rng.chance(0.25) { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,65 @@
+use super::Rng;
+
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// `size` passports, with fields randomly missing or invalid
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| passport(rng))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn passport(rng: &mut Rng) -> String {
+    let mut fields = Vec::new();
+    for key in &["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"] {
+        if !rng.chance(if *key == "cid" { 0.5 } else { 0.9 }) {
+            continue;
+        }
+        let value = if rng.chance(0.9) {
+            valid_value(rng, key)
+        } else {
+            invalid_value(rng, key)
+        };
+        fields.push(format!("{}:{}", key, value));
+    }
+    rng.shuffle(&mut fields);
+
+    // Split the fields over a few lines, as in the real input
+    let mut passport = String::new();
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            passport.push(if rng.chance(0.25) { '\n' } else { ' ' });
+        }
+        passport.push_str(field);
+    }
+    passport
+}
+
+fn valid_value(rng: &mut Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.range(1920..=2002).to_string(),
+        "iyr" => rng.range(2010..=2020).to_string(),
+        "eyr" => rng.range(2020..=2030).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", rng.range(150..=193)),
+        "hgt" => format!("{}in", rng.range(59..=76)),
+        "hcl" => format!("#{:06x}", rng.range(0..=0xff_ffff)),
+        "ecl" => rng.choose(&EYE_COLOURS).to_string(),
+        "pid" => format!("{:09}", rng.range(0..=999_999_999)),
+        _ => rng.range(1..=350).to_string(),
+    }
+}
+
+fn invalid_value(rng: &mut Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.range(1850..=1919).to_string(),
+        "iyr" => rng.range(2021..=2099).to_string(),
+        "eyr" => rng.range(1950..=2019).to_string(),
+        "hgt" if rng.chance(0.5) => format!("{}cm", rng.range(194..=250)),
+        "hgt" => rng.range(59..=76).to_string(),
+        "hcl" => format!("{:06x}", rng.range(0..=0xff_ffff)),
+        "ecl" => "zzz".to_string(),
+        "pid" => format!("{:010}", rng.range(0..=9_999_999_999)),
+        _ => valid_value(rng, key),
+    }
+}
@@ -0,0 +1,49 @@
+use super::Rng;
+
+/// Seat ids are 10 bits, 7 for the row then 3 for the column
+const SEATS: usize = 1 << 10;
+
+/// A full flight of `size` boarding passes, between 3 and 1023, with a single
+/// missing seat somewhere in the middle
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.clamp(3, SEATS - 1);
+    // One more than `size`, as one of the seats is missing
+    let first = rng.range(0..=SEATS - size - 1);
+    let missing = rng.range(first + 1..=first + size - 1);
+    let mut seats: Vec<usize> = (first..=first + size).filter(|id| *id != missing).collect();
+    rng.shuffle(&mut seats);
+    seats
+        .iter()
+        .map(|id| boarding_pass(*id))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn boarding_pass(id: usize) -> String {
+    (0..10)
+        .rev()
+        .map(|bit| {
+            let set = id & (1 << bit) != 0;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_5::parse_seat_information;
+
+    #[test]
+    fn boarding_passes_encode_the_seat_id() {
+        assert_eq!(boarding_pass(357), "FBFBBFFRLR");
+        for id in 0..SEATS {
+            assert_eq!(parse_seat_information(&boarding_pass(id)).id as usize, id);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use super::Rng;
+
+/// `size` groups of between 1 and 5 people, each answering some questions
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // Most people in a group share some answers
+            let common = questions(rng, 0.3);
+            (0..rng.range(1..=5))
+                .map(|_| {
+                    let mut answers = questions(rng, 0.2);
+                    answers.extend(common.iter());
+                    answers.sort_unstable();
+                    answers.dedup();
+                    if answers.is_empty() {
+                        answers.push(b'a' + rng.range(0..=25) as u8);
+                    }
+                    rng.shuffle(&mut answers);
+                    String::from_utf8(answers).expect("answers are lowercase letters")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn questions(rng: &mut Rng, probability: f64) -> Vec<u8> {
+    (b'a'..=b'z').filter(|_| rng.chance(probability)).collect()
+}
@@ -0,0 +1,88 @@
+use super::Rng;
+
+const TARGET: &str = "shiny gold";
+
+const ADJECTIVES: [&str; 20] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy", "dusty", "pastel",
+];
+
+const COLOURS: [&str; 30] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "violet",
+    "white",
+];
+
+/// Bags can only contain bags from a deeper level, this keeps the rules
+/// acyclic and the number of bags inside a shiny gold bag within an `i64`
+const LEVELS: usize = 7;
+
+/// Rules for `size` different bags, between 2 and 600, one of which is
+/// shiny gold
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut bags: Vec<String> = ADJECTIVES
+        .iter()
+        .flat_map(|a| COLOURS.iter().map(move |c| format!("{} {}", a, c)))
+        .filter(|bag| bag != TARGET)
+        .collect();
+    rng.shuffle(&mut bags);
+    bags.truncate(size.max(2) - 1);
+    // In the middle, so it's both inside other bags and has bags inside it
+    bags.insert(bags.len() / 2, TARGET.to_string());
+
+    let level_of = |i: usize| i * LEVELS / bags.len();
+    let mut rules: Vec<String> = bags
+        .iter()
+        .enumerate()
+        .map(|(i, bag)| {
+            let deeper: Vec<_> = (i + 1..bags.len())
+                .filter(|j| level_of(*j) > level_of(i))
+                .collect();
+            if deeper.is_empty() || rng.chance(0.15) {
+                return format!("{} bags contain no other bags.", bag);
+            }
+            let mut contents: Vec<usize> = (0..rng.range(1..=4))
+                .map(|_| *rng.choose(&deeper))
+                .collect();
+            contents.sort_unstable();
+            contents.dedup();
+            let contents: Vec<String> = contents
+                .iter()
+                .map(|j| match rng.range(1..=5) {
+                    1 => format!("1 {} bag", bags[*j]),
+                    n => format!("{} {} bags", n, bags[*j]),
+                })
+                .collect();
+            format!("{} bags contain {}.", bag, contents.join(", "))
+        })
+        .collect();
+    rng.shuffle(&mut rules);
+    rules.join("\n")
+}
@@ -0,0 +1,48 @@
+use super::Rng;
+
+/// A program of `size` instructions, at least 3, which loops forever but
+/// terminates if one `jmp` is changed to a `nop`
+///
+/// Until the broken `jmp` every jump goes forward, and every `nop` would jump
+/// backwards if it were changed, so it's the only fix on the way through the
+/// program. Jumps never leave the program before it's fixed, as part 1 wraps
+/// them around while part 2 doesn't
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.max(3);
+    let broken = rng.range(len / 2..=len - 1);
+    (0..len)
+        .map(|i| {
+            if i == broken {
+                return format!("jmp {}", signed(-(rng.range(1..=i.min(50)) as isize)));
+            }
+            match rng.range(0..=3) {
+                0 | 1 => format!("acc {}", signed(acc_value(rng))),
+                // Skips forward, without jumping over the broken `jmp`
+                2 => {
+                    let limit = if i < broken { broken } else { len };
+                    format!("jmp {}", signed(rng.range(1..=(limit - i).min(8)) as isize))
+                }
+                // Only reached after the broken `jmp` if it's skipped over
+                _ if i > broken => {
+                    let to = rng.range(0..=len - 1);
+                    format!("nop {}", signed(to as isize - i as isize))
+                }
+                _ => format!("nop {}", signed(-(rng.range(0..=i.min(50)) as isize))),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn acc_value(rng: &mut Rng) -> isize {
+    let value = rng.range(1..=50) as isize;
+    if rng.chance(0.5) {
+        -value
+    } else {
+        value
+    }
+}
+
+fn signed(n: isize) -> String {
+    format!("{:+}", n)
+}
@@ -0,0 +1,92 @@
+use super::Rng;
+use std::collections::HashSet;
+
+const PREAMBLE: usize = 25;
+
+/// Past this the numbers overflow an `i64`
+const MAX_SIZE: usize = 1200;
+
+/// `size` numbers, between 27 and 1200, using a preamble of 25
+///
+/// Every number is the sum of two of the 25 before it, apart from the
+/// weakness, which is the sum of a run of earlier numbers instead. Only the
+/// smallest sums are used, even so the numbers double about every 25 lines,
+/// which is why the size is capped
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let len = size.clamp(PREAMBLE + 2, MAX_SIZE);
+    let mut numbers: Vec<u64> = (1..=PREAMBLE as u64 * 2).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(PREAMBLE);
+
+    let weakness = rng.range((PREAMBLE + 1).max(len * 3 / 4)..=len - 1);
+    while numbers.len() < len {
+        let window = &numbers[numbers.len() - PREAMBLE..];
+        let next = if numbers.len() == weakness {
+            weakness_for(rng, &numbers)
+        } else {
+            valid_next(rng, window)
+        };
+        numbers.push(next);
+    }
+    numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// One of the smallest sums of two different numbers in the window, which
+/// isn't already in it. The sum of the two largest numbers never is
+fn valid_next(rng: &mut Rng, window: &[u64]) -> u64 {
+    let mut sums: Vec<u64> = window
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| window[i + 1..].iter().map(move |b| a + b))
+        .filter(|sum| !window.contains(sum))
+        .collect();
+    sums.sort_unstable();
+    sums.dedup();
+    sums.truncate(8);
+    *rng.choose(&sums)
+}
+
+/// The sum of a run of at least two numbers, which isn't the sum of any two
+/// numbers in the last window
+fn weakness_for(rng: &mut Rng, numbers: &[u64]) -> u64 {
+    let window = &numbers[numbers.len() - PREAMBLE..];
+    let sums: HashSet<u64> = window
+        .iter()
+        .flat_map(|a| window.iter().filter(move |b| *b != a).map(move |b| a + b))
+        .collect();
+    loop {
+        let run = rng.range(2..=17.min(numbers.len()));
+        let start = rng.range(0..=numbers.len() - run);
+        let weakness = numbers[start..start + run].iter().sum();
+        if !sums.contains(&weakness) && !window.contains(&weakness) {
+            return weakness;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day_9::generator;
+
+    #[test]
+    fn only_the_weakness_is_invalid() {
+        for seed in 0..5 {
+            let numbers = generator(&generate(&mut Rng::new(seed), 1000)).unwrap();
+            let invalid: Vec<_> = numbers
+                .windows(PREAMBLE + 1)
+                .filter(|w| {
+                    let (last, window) = w.split_last().unwrap();
+                    !window
+                        .iter()
+                        .any(|a| window.iter().any(|b| a != b && a + b == *last))
+                })
+                .collect();
+            assert_eq!(invalid.len(), 1, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,355 @@
+//! Random, well-formed puzzle inputs for fuzzing and benchmarking the
+//! solutions at a larger scale than the real inputs
+//!
+//! Each generator builds its input around the assumptions the day's solution
+//! makes of the real input (e.g. day 8's program can always be fixed with a
+//! single flip), so every generated input should parse and solve
+
+#[cfg(feature = "day1")]
+pub mod day_1;
+#[cfg(feature = "day10")]
+pub mod day_10;
+#[cfg(feature = "day11")]
+pub mod day_11;
+#[cfg(feature = "day12")]
+pub mod day_12;
+#[cfg(feature = "day13")]
+pub mod day_13;
+#[cfg(feature = "day14")]
+pub mod day_14;
+#[cfg(feature = "day15")]
+pub mod day_15;
+#[cfg(feature = "day16")]
+pub mod day_16;
+#[cfg(feature = "day17")]
+pub mod day_17;
+#[cfg(feature = "day18")]
+pub mod day_18;
+#[cfg(feature = "day19")]
+pub mod day_19;
+#[cfg(feature = "day2")]
+pub mod day_2;
+#[cfg(feature = "day20")]
+pub mod day_20;
+#[cfg(feature = "day3")]
+pub mod day_3;
+#[cfg(feature = "day4")]
+pub mod day_4;
+#[cfg(feature = "day5")]
+pub mod day_5;
+#[cfg(feature = "day6")]
+pub mod day_6;
+#[cfg(feature = "day7")]
+pub mod day_7;
+#[cfg(feature = "day8")]
+pub mod day_8;
+#[cfg(feature = "day9")]
+pub mod day_9;
+
+use std::{
+    ops::RangeInclusive,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A small, seedable pseudo random number generator (SplitMix64)
+///
+/// The same seed always produces the same sequence, on every platform, so a
+/// generated input can be recreated from just its seed and size
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds the generator from the current time
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64);
+        Self::new(nanos)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// ## Panics
+    ///
+    /// If the range is empty
+    pub fn range(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot pick from an empty range");
+        let span = (end - start) as u128 + 1;
+        start + ((self.next_u64() as u128 * span) >> 64) as usize
+    }
+
+    /// `true` with the given `probability`, between `0.0` and `1.0`
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // The top 53 bits, as that's all an `f64` can represent exactly
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// ## Panics
+    ///
+    /// If `items` is empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0..=i));
+        }
+    }
+}
+
+pub struct Generator {
+    pub day: u8,
+    /// About the size of the real input
+    pub default_size: usize,
+    /// What `size` means differs for each day, see each day's `generate`
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+/// Every day which has a generator, in order
+pub static GENERATORS: &[Generator] = &[
+    #[cfg(feature = "day1")]
+    Generator {
+        day: 1,
+        default_size: 200,
+        generate: day_1::generate,
+    },
+    #[cfg(feature = "day2")]
+    Generator {
+        day: 2,
+        default_size: 1000,
+        generate: day_2::generate,
+    },
+    #[cfg(feature = "day3")]
+    Generator {
+        day: 3,
+        default_size: 323,
+        generate: day_3::generate,
+    },
+    #[cfg(feature = "day4")]
+    Generator {
+        day: 4,
+        default_size: 250,
+        generate: day_4::generate,
+    },
+    #[cfg(feature = "day5")]
+    Generator {
+        day: 5,
+        default_size: 800,
+        generate: day_5::generate,
+    },
+    #[cfg(feature = "day6")]
+    Generator {
+        day: 6,
+        default_size: 480,
+        generate: day_6::generate,
+    },
+    #[cfg(feature = "day7")]
+    Generator {
+        day: 7,
+        default_size: 590,
+        generate: day_7::generate,
+    },
+    #[cfg(feature = "day8")]
+    Generator {
+        day: 8,
+        default_size: 600,
+        generate: day_8::generate,
+    },
+    #[cfg(feature = "day9")]
+    Generator {
+        day: 9,
+        default_size: 1000,
+        generate: day_9::generate,
+    },
+    #[cfg(feature = "day10")]
+    Generator {
+        day: 10,
+        default_size: 100,
+        generate: day_10::generate,
+    },
+    #[cfg(feature = "day11")]
+    Generator {
+        day: 11,
+        default_size: 95,
+        generate: day_11::generate,
+    },
+    #[cfg(feature = "day12")]
+    Generator {
+        day: 12,
+        default_size: 780,
+        generate: day_12::generate,
+    },
+    #[cfg(feature = "day13")]
+    Generator {
+        day: 13,
+        default_size: 60,
+        generate: day_13::generate,
+    },
+    #[cfg(feature = "day14")]
+    Generator {
+        day: 14,
+        default_size: 100,
+        generate: day_14::generate,
+    },
+    #[cfg(feature = "day15")]
+    Generator {
+        day: 15,
+        default_size: 6,
+        generate: day_15::generate,
+    },
+    #[cfg(feature = "day16")]
+    Generator {
+        day: 16,
+        default_size: 240,
+        generate: day_16::generate,
+    },
+    #[cfg(feature = "day17")]
+    Generator {
+        day: 17,
+        default_size: 8,
+        generate: day_17::generate,
+    },
+    #[cfg(feature = "day18")]
+    Generator {
+        day: 18,
+        default_size: 370,
+        generate: day_18::generate,
+    },
+    #[cfg(feature = "day19")]
+    Generator {
+        day: 19,
+        default_size: 400,
+        generate: day_19::generate,
+    },
+    #[cfg(feature = "day20")]
+    Generator {
+        day: 20,
+        default_size: 12,
+        generate: day_20::generate,
+    },
+];
+
+pub fn find_generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_solution, solution::Part, YEAR};
+
+    #[test]
+    fn rng_is_reproducible() {
+        let mut a = Rng::new(2020);
+        let mut b = Rng::new(2020);
+        let a: Vec<_> = (0..10).map(|_| a.next_u64()).collect();
+        let b: Vec<_> = (0..10).map(|_| b.next_u64()).collect();
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            (0..10)
+                .map(|_| Rng::new(2021).next_u64())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn range_is_inclusive() {
+        let mut rng = Rng::new(1);
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let n = rng.range(3..=6);
+            seen[n - 3] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+        assert_eq!(rng.range(7..=7), 7);
+    }
+
+    /// Days without any answers yet, such as one which was just added with
+    /// `aoc new`, don't need one until they're solved
+    #[test]
+    fn every_day_has_a_generator() {
+        use crate::answers::Answers;
+
+        let answers = Answers::load(YEAR).unwrap();
+        let days: Vec<u8> = GENERATORS.iter().map(|g| g.day).collect();
+        let solutions: Vec<u8> = crate::SOLUTIONS.iter().map(|s| s.day()).collect();
+        let answered: Vec<u8> = solutions
+            .iter()
+            .copied()
+            .filter(|day| {
+                Part::ALL
+                    .iter()
+                    .any(|part| answers.get(*day, *part).is_some())
+            })
+            .collect();
+        assert!(days.iter().all(|day| solutions.contains(day)), "{:?}", days);
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{:?}", days);
+        assert!(
+            answered.iter().all(|day| days.contains(day)),
+            "{:?}",
+            answered
+        );
+    }
+
+    /// Parts which can't be run against a generated input
+    fn skipped(day: u8, part: Part) -> bool {
+        match (day, part) {
+            // 30 million turns, this is already covered by the real input
+            (15, Part::Two) => true,
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn implementations_agree_on_generated_inputs() {
+        for generator in GENERATORS {
+            let solution = find_solution(YEAR, generator.day).unwrap();
+            for seed in 0..3 {
+                let mut rng = Rng::new(seed);
+                let input = (generator.generate)(&mut rng, generator.default_size);
+                let parsed = solution
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("seed {}: {}", seed, e));
+                let config = solution.default_config();
+                for part in Part::ALL.iter().filter(|p| !skipped(generator.day, **p)) {
+                    let expected = solution.solve(*part, parsed.as_ref(), config.as_ref());
+                    for name in solution.implementations(*part) {
+                        assert_eq!(
+                            solution.solve_with(*part, name, parsed.as_ref(), config.as_ref()),
+                            Some(expected.clone()),
+                            "day {} part {} ({}), seed {}",
+                            generator.day,
+                            part,
+                            name,
+                            seed
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for generator in GENERATORS {
+            let a = (generator.generate)(&mut Rng::new(7), generator.default_size);
+            let b = (generator.generate)(&mut Rng::new(7), generator.default_size);
+            assert_eq!(a, b, "day {}", generator.day);
+        }
+    }
+}
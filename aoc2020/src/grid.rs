@@ -0,0 +1,540 @@
+use crate::parse::{parse_lines, LineError, ParseError};
+use std::{
+    convert::TryFrom,
+    fmt,
+    iter::successors,
+    ops::{Index, IndexMut},
+};
+
+/// `(row, column)` counted from the top left, NOT `(x, y)`
+pub type Point = (usize, usize);
+
+/// `(rows, columns)` to move by, rows increase going down the grid
+pub type Direction = (isize, isize);
+
+pub const ORTHOGONAL: [Direction; 4] = [
+    (-1, 0), // Up
+    (0, 1),  // Right
+    (1, 0),  // Down
+    (0, -1), // Left
+];
+
+pub const ADJACENT: [Direction; 8] = [
+    (-1, -1), // Top left
+    (-1, 0),  // Top
+    (-1, 1),  // Top right
+    (0, 1),   // Right
+    (1, 1),   // Bottom Right
+    (1, 0),   // Bottom
+    (1, -1),  // Bottom Left
+    (0, -1),  // Left
+];
+
+/// One of the eight ways a grid can be turned around and over, the symmetries
+/// of a square
+///
+/// Each is the same as mirroring the grid left to right or not, then turning
+/// it clockwise a number of quarter turns, see [`Orientation::turns`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Orientation {
+    #[default]
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrored left to right
+    FlipHorizontal,
+    /// Mirrored across the diagonal from the top right to the bottom left
+    AntiTranspose,
+    /// Mirrored top to bottom
+    FlipVertical,
+    /// Mirrored across the diagonal from the top left to the bottom right
+    Transpose,
+}
+
+impl Orientation {
+    /// Every orientation, in the order of [`Orientation::from_turns`]
+    pub const ALL: [Orientation; 8] = [
+        Orientation::Identity,
+        Orientation::Rotate90,
+        Orientation::Rotate180,
+        Orientation::Rotate270,
+        Orientation::FlipHorizontal,
+        Orientation::AntiTranspose,
+        Orientation::FlipVertical,
+        Orientation::Transpose,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Orientation> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Mirrors left to right if `flipped`, then turns `turns` quarter turns
+    /// clockwise
+    pub fn from_turns(turns: u8, flipped: bool) -> Self {
+        Self::ALL[usize::from(flipped) * 4 + usize::from(turns % 4)]
+    }
+
+    /// The `(turns, flipped)` which make up this orientation
+    pub fn turns(self) -> (u8, bool) {
+        let i = Self::ALL.iter().position(|o| *o == self).unwrap_or(0) as u8;
+        (i % 4, i >= 4)
+    }
+
+    /// This orientation followed by `next`
+    pub fn then(self, next: Orientation) -> Self {
+        let (turns, flipped) = self.turns();
+        let (next_turns, next_flipped) = next.turns();
+        // Mirroring after turning is the same as turning the other way after
+        // mirroring
+        let turns = if next_flipped {
+            next_turns + 4 - turns
+        } else {
+            next_turns + turns
+        };
+        Self::from_turns(turns, flipped != next_flipped)
+    }
+
+    /// The orientation which undoes this one
+    pub fn inverse(self) -> Self {
+        match self.turns() {
+            // Mirror images undo themselves
+            (_, true) => self,
+            (turns, false) => Self::from_turns(4 - turns, false),
+        }
+    }
+
+    /// Whether a `width` by `height` grid becomes `height` by `width`
+    pub fn swaps_sides(self) -> bool {
+        self.turns().0 % 2 == 1
+    }
+
+    /// Where `point` of a `width` by `height` grid ends up once the grid is
+    /// in this orientation
+    pub fn apply(self, (row, col): Point, width: usize, height: usize) -> Point {
+        let (turns, flipped) = self.turns();
+        let (mut row, mut col) = if flipped {
+            (row, width - 1 - col)
+        } else {
+            (row, col)
+        };
+        let (mut width, mut height) = (width, height);
+        for _ in 0..turns {
+            let turned = (col, height - 1 - row);
+            row = turned.0;
+            col = turned.1;
+            std::mem::swap(&mut width, &mut height);
+        }
+        (row, col)
+    }
+}
+
+/// A rectangular grid stored as a single row-major `Vec`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// ## Panics
+    ///
+    /// If there aren't exactly `width * height` cells
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// ## Panics
+    ///
+    /// If the rows aren't all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "every row should be the same length"
+        );
+        Self::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, col): Point) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if self.contains(point) {
+            Some(&self.cells[self.index(point)])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            let i = self.index(point);
+            Some(&mut self.cells[i])
+        } else {
+            None
+        }
+    }
+
+    /// Indexes the grid as if it repeats forever in every direction
+    ///
+    /// ## Panics
+    ///
+    /// If the grid is empty
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        let row = row.rem_euclid(self.height as isize) as usize;
+        let col = col.rem_euclid(self.width as isize) as usize;
+        &self[(row, col)]
+    }
+
+    /// The point one step in `direction` from `point`, if it's in the grid
+    pub fn step(&self, (row, col): Point, (d_row, d_col): Direction) -> Option<Point> {
+        let row = row as isize + d_row;
+        let col = col as isize + d_col;
+        if row < 0 || col < 0 || row >= self.height as isize || col >= self.width as isize {
+            return None;
+        }
+        Some((row as usize, col as usize))
+    }
+
+    /// The points above, below, left and right of `point` which are in the grid
+    pub fn neighbours_4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ORTHOGONAL.iter().filter_map(move |d| self.step(point, *d))
+    }
+
+    /// The points surrounding `point`, including diagonals, which are in the grid
+    pub fn neighbours_8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        ADJACENT.iter().filter_map(move |d| self.step(point, *d))
+    }
+
+    /// Every point from `point` in `direction` until the edge of the grid,
+    /// not including `point` itself
+    pub fn ray(&self, point: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        successors(self.step(point, direction), move |p| {
+            self.step(*p, direction)
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on 0, and an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(col).step_by(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every cell along with its point, row by row
+    pub fn points(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width, i % width), cell))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    pub fn rotate_clockwise(&mut self)
+    where
+        T: Clone,
+    {
+        let (width, height) = (self.height, self.width);
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[(self.height - 1 - col, row)].clone())
+            .collect();
+        *self = Self::new(width, height, cells);
+    }
+
+    /// Mirrors the grid left to right
+    pub fn flip_horizontal(&mut self) {
+        for row in self.cells.chunks_mut(self.width.max(1)) {
+            row.reverse();
+        }
+    }
+
+    /// Mirrors the grid top to bottom
+    pub fn flip_vertical(&mut self) {
+        for row in 0..self.height / 2 {
+            let other = self.height - 1 - row;
+            for col in 0..self.width {
+                self.cells
+                    .swap(row * self.width + col, other * self.width + col);
+            }
+        }
+    }
+
+    /// `(width, height)` of the grid in `orientation`
+    pub fn oriented_size(&self, orientation: Orientation) -> (usize, usize) {
+        if orientation.swaps_sides() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// The cell at `point` as if the grid were in `orientation`, without
+    /// moving any cells
+    pub fn get_oriented(&self, orientation: Orientation, point: Point) -> Option<&T> {
+        let (width, height) = self.oriented_size(orientation);
+        if point.0 >= height || point.1 >= width {
+            return None;
+        }
+        self.get(orientation.inverse().apply(point, width, height))
+    }
+
+    /// A copy of the grid in `orientation`
+    pub fn oriented(&self, orientation: Orientation) -> Self
+    where
+        T: Clone,
+    {
+        let (width, height) = self.oriented_size(orientation);
+        let cells = (0..width * height)
+            .map(|i| {
+                let point = orientation
+                    .inverse()
+                    .apply((i / width, i % width), width, height);
+                self[point].clone()
+            })
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    fn index(&self, (row, col): Point) -> usize {
+        row * self.width + col
+    }
+}
+
+impl<T> Grid<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    /// Parses a map with one character per cell, blank lines are skipped
+    ///
+    /// If a character can't be converted, the conversion's error is used as
+    /// the message of the [`ParseError`]
+    pub fn parse(day: u8, input: &str) -> Result<Self, ParseError> {
+        let mut width = None;
+        let rows = parse_lines(day, input, |line| {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    T::try_from(c)
+                        .map_err(|e| LineError::new(&line[i..i + c.len_utf8()], e.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let width = *width.get_or_insert(row.len());
+            if row.len() != width {
+                return Err(LineError::new(line, format!("expected {} columns", width)));
+            }
+            Ok(row)
+        })?;
+        if rows.is_empty() {
+            return Err(ParseError::unexpected_end(day, 1, "expected a grid"));
+        }
+        Ok(Self::from_rows(rows))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        assert!(
+            self.contains(point),
+            "{:?} is outside of a {}x{} grid",
+            point,
+            self.width,
+            self.height
+        );
+        &self.cells[Grid::index(self, point)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .expect("point should be inside the grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(0, input).unwrap()
+    }
+
+    #[test]
+    fn parses_and_renders() {
+        let g = grid("ab\ncd\nef");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g[(2, 0)], 'e');
+        assert_eq!(g.get((3, 0)), None);
+        assert_eq!(g.to_string(), "ab\ncd\nef");
+        assert_eq!(g.column(1).collect::<String>(), "bdf");
+    }
+
+    #[test]
+    fn reports_ragged_rows() {
+        let error = Grid::<char>::parse(0, "ab\nc").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.message, "expected 2 columns");
+    }
+
+    #[test]
+    fn wraps_in_every_direction() {
+        let g = grid("ab\ncd");
+        assert_eq!(*g.get_wrapping(3, 4), 'c');
+        assert_eq!(*g.get_wrapping(-1, -1), 'd');
+    }
+
+    #[test]
+    fn neighbours_stay_inside_the_grid() {
+        let g = grid("abc\ndef\nghi");
+        assert_eq!(
+            g.neighbours_4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(g.neighbours_8((0, 0)).count(), 3);
+        assert_eq!(g.neighbours_8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn casts_rays_to_the_edge() {
+        let g = grid("abc\ndef\nghi");
+        let diagonal: String = g.ray((0, 0), (1, 1)).map(|p| g[p]).collect();
+        assert_eq!(diagonal, "ei");
+        assert_eq!(g.ray((0, 0), (-1, 0)).count(), 0);
+    }
+
+    #[test]
+    fn rotates_and_flips() {
+        let mut g = grid("abc\ndef");
+        g.rotate_clockwise();
+        assert_eq!(g.to_string(), "da\neb\nfc");
+        g.flip_horizontal();
+        assert_eq!(g.to_string(), "ad\nbe\ncf");
+        g.flip_vertical();
+        assert_eq!(g.to_string(), "cf\nbe\nad");
+    }
+
+    #[test]
+    fn orientations_match_turning_and_flipping() {
+        let g = grid("abc\ndef");
+        let mut turned = g.clone();
+        for orientation in Orientation::all() {
+            if orientation == Orientation::FlipHorizontal {
+                turned.flip_horizontal();
+            }
+            assert_eq!(g.oriented(orientation), turned, "{:?}", orientation);
+            turned.rotate_clockwise();
+        }
+        assert_eq!(
+            g.oriented(Orientation::FlipVertical).to_string(),
+            "def\nabc"
+        );
+        assert_eq!(g.oriented(Orientation::Transpose).to_string(), "ad\nbe\ncf");
+        assert_eq!(
+            g.oriented(Orientation::AntiTranspose).to_string(),
+            "fc\neb\nda"
+        );
+    }
+
+    #[test]
+    fn orientations_compose_and_invert() {
+        let g = grid("abc\ndef");
+        for a in Orientation::all() {
+            assert_eq!(g.oriented(a).oriented(a.inverse()), g, "{:?}", a);
+            assert_eq!(a.then(a.inverse()), Orientation::Identity);
+            for b in Orientation::all() {
+                assert_eq!(
+                    g.oriented(a).oriented(b),
+                    g.oriented(a.then(b)),
+                    "{:?} then {:?}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reads_cells_in_any_orientation() {
+        let g = grid("abc\ndef");
+        for orientation in Orientation::all() {
+            let oriented = g.oriented(orientation);
+            for (point, cell) in oriented.points() {
+                assert_eq!(g.get_oriented(orientation, point), Some(cell));
+            }
+            let (width, height) = g.oriented_size(orientation);
+            assert_eq!(g.get_oriented(orientation, (height, 0)), None);
+            assert_eq!(g.get_oriented(orientation, (0, width)), None);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! Writing grids out as binary Netpbm images, which most image viewers can
+//! open without anything else installed
+
+use crate::grid::Grid;
+
+/// Red, green and blue
+pub type Rgb = [u8; 3];
+
+/// A greyscale image (`P5`), one byte per cell
+pub fn pgm(grid: &Grid<u8>) -> Vec<u8> {
+    let mut image = header("P5", grid);
+    image.extend(grid.iter());
+    image
+}
+
+/// A colour image (`P6`), three bytes per cell
+pub fn ppm(grid: &Grid<Rgb>) -> Vec<u8> {
+    let mut image = header("P6", grid);
+    image.extend(grid.iter().flatten());
+    image
+}
+
+fn header<T>(magic: &str, grid: &Grid<T>) -> Vec<u8> {
+    format!("{}\n{} {}\n255\n", magic, grid.width(), grid.height()).into_bytes()
+}
+
+/// Every cell blown up to `scale` cells square, so small grids are big
+/// enough to see
+pub fn scaled<T: Clone>(grid: &Grid<T>, scale: usize) -> Grid<T> {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let cells = (0..width * height)
+        .map(|i| grid[(i / width / scale, i % width / scale)].clone())
+        .collect();
+    Grid::new(width, height, cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_headers_and_pixels() {
+        let grey = Grid::new(2, 1, vec![0, 255]);
+        assert_eq!(pgm(&grey), b"P5\n2 1\n255\n\x00\xff");
+        let colour = Grid::new(1, 2, vec![[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(ppm(&colour), b"P6\n1 2\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[test]
+    fn scales_every_cell() {
+        let grid = Grid::new(2, 1, vec!['a', 'b']);
+        assert_eq!(scaled(&grid, 2).to_string(), "aabb\naabb");
+        assert_eq!(scaled(&grid, 1), grid);
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    env,
+    error::Error,
+    fmt, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable which overrides the directory inputs are loaded from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Inputs are stored as `{root}/{year}/day{day}.txt`
+pub const DEFAULT_INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+
+/// Returned when an input can't be read
+#[derive(Debug)]
+pub struct InputError {
+    /// `-` if the input was being read from stdin
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path == Path::new("-") {
+            write!(f, "failed to read input from stdin: {}", self.source)
+        } else {
+            write!(
+                f,
+                "failed to read input `{}`: {}",
+                self.path.display(),
+                self.source
+            )
+        }
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// The directory inputs are loaded from, `$AOC_INPUT_DIR` if it's set,
+/// otherwise the `input` directory of this crate
+pub fn input_dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+}
+
+pub fn input_path(root: impl AsRef<Path>, year: u16, day: u8) -> PathBuf {
+    root.as_ref()
+        .join(year.to_string())
+        .join(format!("day{}.txt", day))
+}
+
+/// Loads the input for `year` and `day` from [`input_dir`]
+pub fn load_input(year: u16, day: u8) -> Result<String, InputError> {
+    load_input_from(input_dir(), year, day)
+}
+
+/// Loads the input for `year` and `day` from the directory `root`
+pub fn load_input_from(root: impl AsRef<Path>, year: u16, day: u8) -> Result<String, InputError> {
+    read_input(input_path(root, year, day))
+}
+
+/// Someone's own inputs, for checking the solutions don't only work for one
+/// input. They're stored as `{root}/{year}/{name}/day{day}.txt`, with their
+/// answers in `answers.toml` next to them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedInputs {
+    pub name: String,
+    pub dir: PathBuf,
+}
+
+impl NamedInputs {
+    pub fn new(root: impl AsRef<Path>, year: u16, name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            dir: root.as_ref().join(year.to_string()).join(name),
+        }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{}.txt", day))
+    }
+
+    pub fn load(&self, day: u8) -> Result<String, InputError> {
+        read_input(self.input_path(day))
+    }
+
+    /// Whether there's an input for `day`, not everyone has every day
+    pub fn has(&self, day: u8) -> bool {
+        self.input_path(day).is_file()
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.dir.join("answers.toml")
+    }
+}
+
+/// Every set of [`NamedInputs`] for `year` in `root`, sorted by name
+pub fn named_inputs(root: impl AsRef<Path>, year: u16) -> Result<Vec<NamedInputs>, InputError> {
+    let dir = root.as_ref().join(year.to_string());
+    let error = |source| InputError {
+        path: dir.clone(),
+        source,
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(error(e)),
+    };
+    let mut named = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(error)?;
+        if entry.file_type().map_err(error)?.is_dir() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            named.push(NamedInputs::new(root.as_ref(), year, &name));
+        }
+    }
+    named.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(named)
+}
+
+/// Reads the input at `path`, or from stdin if the path is `-`
+///
+/// The input is passed through [`normalise`]
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let path = path.as_ref();
+    let error = |source| InputError {
+        path: path.to_path_buf(),
+        source,
+    };
+    let input = if path == Path::new("-") {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf).map_err(error)?;
+        buf
+    } else {
+        fs::read_to_string(path).map_err(error)?
+    };
+    Ok(normalise(&input))
+}
+
+/// Converts CRLF line endings to LF and strips any trailing blank lines,
+/// including the final newline, the same as `cargo-aoc` does
+pub fn normalise(input: &str) -> String {
+    let input = input.replace("\r\n", "\n");
+    let last_line = input.trim_end().len();
+    let end = input[last_line..]
+        .find('\n')
+        .map_or(input.len(), |i| last_line + i);
+    input[..end].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+
+    #[test]
+    fn normalise_strips_carriage_returns_and_trailing_blank_lines() {
+        assert_eq!(normalise("1\r\n2\r\n\r\n"), "1\n2");
+        assert_eq!(normalise("a\n\nb  \n \n\n"), "a\n\nb  ");
+        assert_eq!(normalise("\n\n"), "");
+    }
+
+    #[test]
+    fn missing_input_is_an_error() {
+        let error = load_input_from("does/not/exist", 2020, 1).unwrap_err();
+        assert_eq!(error.path, Path::new("does/not/exist/2020/day1.txt"));
+        assert_eq!(error.source.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn loads_input_by_year_and_day() {
+        let input = load_input_from(DEFAULT_INPUT_DIR, 2020, 1).unwrap();
+        assert!(!input.ends_with('\n'));
+        assert!(input.lines().all(|l| l.parse::<u32>().is_ok()));
+    }
+
+    #[test]
+    fn finds_named_inputs() {
+        let root = TempDir::new("named");
+        for name in ["bob", "alice"].iter() {
+            fs::create_dir_all(root.join("2020").join(name)).unwrap();
+        }
+        fs::write(root.join("2020/day1.txt"), "1").unwrap();
+        fs::write(root.join("2020/alice/day1.txt"), "1\r\n2\r\n").unwrap();
+
+        let named = named_inputs(&root, 2020).unwrap();
+        let names: Vec<_> = named.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        assert_eq!(named[0].load(1).unwrap(), "1\n2");
+        assert!(named[0].has(1) && !named[0].has(2) && !named[1].has(1));
+        assert_eq!(named[1].answers_path(), root.join("2020/bob/answers.toml"));
+        assert!(named_inputs(&root, 2021).unwrap().is_empty());
+    }
+}
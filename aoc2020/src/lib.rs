@@ -4,38 +4,188 @@ extern crate aoc_runner;
 #[macro_use]
 extern crate aoc_runner_derive;
 
+// Declared first so the macros are in scope in every other module
+#[cfg(test)]
+#[macro_use]
+mod macros;
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+#[cfg(feature = "day1")]
 pub mod day_1;
+#[cfg(feature = "day10")]
 pub mod day_10;
+#[cfg(feature = "day11")]
 pub mod day_11;
+#[cfg(feature = "day12")]
 pub mod day_12;
+#[cfg(feature = "day13")]
 pub mod day_13;
+#[cfg(feature = "day14")]
 pub mod day_14;
+#[cfg(feature = "day15")]
 pub mod day_15;
+#[cfg(feature = "day16")]
 pub mod day_16;
+#[cfg(feature = "day17")]
 pub mod day_17;
+#[cfg(feature = "day18")]
 pub mod day_18;
+#[cfg(feature = "day19")]
 pub mod day_19;
+#[cfg(feature = "day2")]
 pub mod day_2;
+#[cfg(feature = "day20")]
 pub mod day_20;
+#[cfg(feature = "day3")]
 pub mod day_3;
+#[cfg(feature = "day4")]
 pub mod day_4;
+#[cfg(feature = "day5")]
 pub mod day_5;
+#[cfg(feature = "day6")]
 pub mod day_6;
+#[cfg(feature = "day7")]
 pub mod day_7;
+#[cfg(feature = "day8")]
 pub mod day_8;
+#[cfg(feature = "day9")]
 pub mod day_9;
+pub mod gen;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod memory;
+pub mod metrics;
+pub mod parse;
+pub mod pattern;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+#[cfg(test)]
+mod temp_dir;
+
+pub use answer::Answer;
+pub use input::{load_input, InputError};
+pub use parse::ParseError;
+
+use solution::Solver;
+
+pub const YEAR: u16 = 2020;
+
+/// Every day which has a solution, in order
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    #[cfg(feature = "day1")]
+    &day_1::Day1,
+    #[cfg(feature = "day2")]
+    &day_2::Day2,
+    #[cfg(feature = "day3")]
+    &day_3::Day3,
+    #[cfg(feature = "day4")]
+    &day_4::Day4,
+    #[cfg(feature = "day5")]
+    &day_5::Day5,
+    #[cfg(feature = "day6")]
+    &day_6::Day6,
+    #[cfg(feature = "day7")]
+    &day_7::Day7,
+    #[cfg(feature = "day8")]
+    &day_8::Day8,
+    #[cfg(feature = "day9")]
+    &day_9::Day9,
+    #[cfg(feature = "day10")]
+    &day_10::Day10,
+    #[cfg(feature = "day11")]
+    &day_11::Day11,
+    #[cfg(feature = "day12")]
+    &day_12::Day12,
+    #[cfg(feature = "day13")]
+    &day_13::Day13,
+    #[cfg(feature = "day14")]
+    &day_14::Day14,
+    #[cfg(feature = "day15")]
+    &day_15::Day15,
+    #[cfg(feature = "day16")]
+    &day_16::Day16,
+    #[cfg(feature = "day17")]
+    &day_17::Day17,
+    #[cfg(feature = "day18")]
+    &day_18::Day18,
+    #[cfg(feature = "day19")]
+    &day_19::Day19,
+    #[cfg(feature = "day20")]
+    &day_20::Day20,
+];
 
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-    path::Path,
-};
-
-pub fn get_input_from_file<P: AsRef<Path>>(path: P) -> String {
-    let file = File::open(path).expect("file failed to open");
-    let mut buf = String::new();
-    BufReader::new(file).read_to_string(&mut buf).ok();
-    buf
+/// Looks up the solution for a given `year` and `day`
+pub fn find_solution(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.year() == year && s.day() == day)
+        .copied()
 }
 
 aoc_lib! { year = 2020 }
+
+// Each of these needs day 1 at least
+#[cfg(all(test, feature = "day1"))]
+mod tests {
+    use super::*;
+    use solution::Part;
+
+    #[test]
+    #[cfg(feature = "all")]
+    fn solutions_are_ordered_and_unique() {
+        let keys: Vec<_> = SOLUTIONS.iter().map(|s| (s.year(), s.day())).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]), "{:?}", keys);
+        assert!(
+            keys.iter()
+                .all(|(year, day)| *year == YEAR && (1..=25).contains(day)),
+            "{:?}",
+            keys
+        );
+    }
+
+    #[test]
+    fn find_solution_runs_day() {
+        let solution = find_solution(2020, 1).expect("day 1 should be registered");
+        let input = "1721\n979\n366\n299\n675\n1456";
+        assert_eq!(
+            solution.run(Part::One, input).unwrap(),
+            Answer::from(514579)
+        );
+        assert_eq!(
+            solution.run(Part::Two, input).unwrap(),
+            Answer::from(241861950)
+        );
+        assert!(find_solution(2020, 25).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "day2")]
+    fn alternatives_are_listed_after_main() {
+        let solution = find_solution(2020, 1).unwrap();
+        assert_eq!(
+            solution.implementations(Part::One),
+            vec![solution::MAIN, "two pointer"]
+        );
+        let input = solution.parse("1721\n979\n366\n299\n675\n1456").unwrap();
+        let config = solution.default_config();
+        assert_eq!(
+            solution.solve_with(Part::One, "two pointer", input.as_ref(), config.as_ref()),
+            Some(Answer::from(514579))
+        );
+        assert_eq!(
+            solution.solve_with(Part::One, "hash set", input.as_ref(), config.as_ref()),
+            None
+        );
+        assert_eq!(
+            find_solution(2020, 2).unwrap().implementations(Part::Two),
+            vec![solution::MAIN]
+        );
+    }
+}
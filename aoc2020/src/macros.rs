@@ -0,0 +1,128 @@
+use crate::{
+    solution::{Part, Solution},
+    Answer,
+};
+
+/// Generates a test for each example, checking the answers of a day's parts
+///
+/// ```ignore
+/// aoc_test! {
+///     Day1,
+///     example: "1721\n979\n366\n299\n675\n1456" => [514579, 241861950],
+///     // `_` skips a part, and part 2 can be left off entirely
+///     example_2: TEST_INPUT_2 => [_, 12],
+///     // Runs with a config other than the day's default
+///     other_target: TEST_INPUT, Config { target_sum: 1340 } => [0, 73867950],
+///     // Runs against the real input, loaded with `crate::input::load_input`.
+///     // Answers recorded in `answers/` are already checked by `crate::answers`
+///     real => [211899, 275765682],
+/// }
+/// ```
+///
+/// Answers can be numbers, strings or parenthesised expressions, they're
+/// parsed with [`Answer::parse`] and compared with the output of the
+/// [`Solution`]. Every alternative implementation of a part has to give the
+/// same answer
+///
+/// [`Answer::parse`]: crate::Answer::parse
+/// [`Solution`]: crate::solution::Solution
+// Unused if every day has been left out
+#[allow(unused_macros)]
+macro_rules! aoc_test {
+    (@tests $solution:ty $(,)?) => {};
+    (@tests $solution:ty, real => [$($answer:tt),+ $(,)?] $(, $($rest:tt)*)?) => {
+        #[test]
+        #[allow(unused_parens)]
+        fn real_input() {
+            let input = $crate::input::load_input(
+                <$solution as $crate::solution::Solution>::YEAR,
+                <$solution as $crate::solution::Solution>::DAY,
+            )
+            .unwrap();
+            aoc_test!(@parts $solution, &input, Default::default(), [$($answer),+]);
+        }
+        aoc_test!(@tests $solution, $($($rest)*)?);
+    };
+    (@tests $solution:ty, $name:ident: $input:expr => [$($answer:tt),+ $(,)?] $(, $($rest:tt)*)?) => {
+        #[test]
+        #[allow(unused_parens)]
+        fn $name() {
+            aoc_test!(@parts $solution, $input, Default::default(), [$($answer),+]);
+        }
+        aoc_test!(@tests $solution, $($($rest)*)?);
+    };
+    (@tests $solution:ty, $name:ident: $input:expr, $config:expr => [$($answer:tt),+ $(,)?] $(, $($rest:tt)*)?) => {
+        #[test]
+        #[allow(unused_parens)]
+        fn $name() {
+            aoc_test!(@parts $solution, $input, $config, [$($answer),+]);
+        }
+        aoc_test!(@tests $solution, $($($rest)*)?);
+    };
+
+    (@parts $solution:ty, $input:expr, $config:expr, [$part_1:tt]) => {
+        aoc_test!(@parts $solution, $input, $config, [$part_1, _]);
+    };
+    (@parts $solution:ty, $input:expr, $config:expr, [$part_1:tt, $part_2:tt]) => {
+        // Still parsed if every part is skipped, so the input is checked
+        #[allow(unused_variables)]
+        let input = <$solution as $crate::solution::Solution>::parse($input).unwrap();
+        #[allow(unused_variables)]
+        let config: <$solution as $crate::solution::Solution>::Config = $config;
+        aoc_test!(@check $solution, One, &input, &config, $part_1);
+        aoc_test!(@check $solution, Two, &input, &config, $part_2);
+    };
+
+    (@check $solution:ty, $part:ident, $input:expr, $config:expr, _) => {};
+    (@check $solution:ty, $part:ident, $input:expr, $config:expr, $expected:tt) => {
+        $crate::macros::check::<$solution>(
+            $crate::solution::Part::$part,
+            $input,
+            $config,
+            $crate::macros::expected($expected),
+        );
+    };
+
+    ($solution:ty, $($rest:tt)*) => {
+        aoc_test!(@tests $solution, $($rest)*);
+    };
+}
+
+/// Checks the main implementation of `part`, and every alternative to it,
+/// gives the `expected` answer with the given `config`
+pub fn check<S: Solution>(part: Part, input: &S::Input, config: &S::Config, expected: Answer) {
+    let actual = match part {
+        Part::One => S::part_1(input, config),
+        Part::Two => S::part_2(input, config),
+    };
+    assert_eq!(actual, expected, "part {}", part);
+    for alternative in S::ALTERNATIVES.iter().filter(|a| a.part == part) {
+        let actual = (alternative.solve)(input, config);
+        assert_eq!(actual, expected, "part {} ({})", part, alternative.name);
+    }
+}
+
+/// An answer expected by [`aoc_test!`]
+///
+/// This is only implemented for one integer type so that integer literals are
+/// inferred as it, rather than defaulting to an `i32` which most answers
+/// overflow
+pub trait Expected {
+    fn into_answer(self) -> Answer;
+}
+
+impl Expected for i128 {
+    fn into_answer(self) -> Answer {
+        Answer::parse(&self.to_string())
+    }
+}
+
+impl Expected for &str {
+    fn into_answer(self) -> Answer {
+        Answer::parse(self)
+    }
+}
+
+pub fn expected(answer: impl Expected) -> Answer {
+    answer.into_answer()
+}
@@ -0,0 +1,189 @@
+//! Opt-in accounting of heap allocations, enabled with the `alloc-stats`
+//! feature
+//!
+//! ```sh
+//! cargo run --release --features alloc-stats --bin aoc -- run 18
+//! ```
+//!
+//! The counts are kept per thread, so parts being solved at the same time on
+//! other threads don't show up in each other's numbers
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+};
+
+/// Whether allocations are being counted at all
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+/// The heap usage of a piece of code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    /// Allocations and reallocations made
+    pub count: u64,
+    /// Bytes requested over all of them, nothing is taken off for frees
+    pub bytes: u64,
+    /// The most memory which was live at once, over what was live before
+    pub peak: u64,
+}
+
+impl fmt::Display for Allocations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!(
+            "{} allocs, {} total, {} peak",
+            self.count,
+            Bytes(self.bytes),
+            Bytes(self.peak)
+        ))
+    }
+}
+
+/// Bytes in the largest unit they're at least one of
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut size = self.0 as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            write!(f, "{} B", self.0)
+        } else {
+            write!(f, "{:.1} {}", size, UNITS[unit])
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Counters {
+    count: u64,
+    bytes: u64,
+    /// Can go below zero on a thread which frees memory another allocated
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters { count: 0, bytes: 0, live: 0, peak: 0 })
+    };
+}
+
+fn update(f: impl FnOnce(&mut Counters)) {
+    // The thread local is gone while the thread is being torn down, whatever
+    // is allocated then isn't of interest
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        f(&mut c);
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+/// Hands every request to the [`System`] allocator, counting them on the way
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        update(|c| c.live -= layout.size() as i64);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            update(|c| {
+                c.count += 1;
+                c.bytes += new_size as u64;
+                c.live += new_size as i64 - layout.size() as i64;
+            });
+        }
+        new
+    }
+}
+
+fn allocated(size: usize) {
+    update(|c| {
+        c.count += 1;
+        c.bytes += size as u64;
+        c.live += size as i64;
+    });
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Runs `f`, counting what it allocates on the current thread. The
+/// allocations are `None` unless the `alloc-stats` feature is enabled
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Allocations>) {
+    let before = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak = c.live;
+        counters.set(c);
+        c
+    });
+    let result = f();
+    let after = COUNTERS.with(Cell::get);
+    let allocations = Allocations {
+        count: after.count - before.count,
+        bytes: after.bytes - before.bytes,
+        peak: (after.peak - before.live).max(0) as u64,
+    };
+    (result, Some(allocations).filter(|_| ENABLED))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sizes() {
+        let allocations = Allocations {
+            count: 3,
+            bytes: 3 * 1024 * 1024 / 2,
+            peak: 512,
+        };
+        assert_eq!(
+            allocations.to_string(),
+            "3 allocs, 1.5 MiB total, 512 B peak"
+        );
+    }
+
+    #[test]
+    fn counts_this_thread() {
+        let (_, allocations) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(128);
+            v.extend(0..128);
+            drop(v);
+            vec![0u8; 16]
+        });
+        assert_eq!(allocations.is_some(), ENABLED);
+        if let Some(allocations) = allocations {
+            assert_eq!(allocations.count, 2);
+            assert_eq!(allocations.bytes, 128 * 8 + 16);
+            assert_eq!(allocations.peak, 128 * 8);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Counters and spans which solutions can bump to show how much work they
+//! did, independently of how fast the machine happened to be at the time.
+//! They cost nothing unless the `metrics` feature is enabled
+//!
+//! ```sh
+//! cargo run --release --features metrics --bin aoc -- run 8
+//! ```
+//!
+//! Like the allocation counts in [`memory`](crate::memory) they're kept per
+//! thread, so each part only sees its own
+
+use serde::Serialize;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Whether anything is being recorded at all
+pub const ENABLED: bool = cfg!(feature = "metrics");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SpanStats {
+    pub calls: u64,
+    #[serde(rename = "total_ns", with = "crate::bench::nanos")]
+    pub total: Duration,
+}
+
+/// Everything recorded while a part was being solved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Metrics {
+    pub counters: BTreeMap<&'static str, u64>,
+    pub spans: BTreeMap<&'static str, SpanStats>,
+}
+
+impl Metrics {
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty() && self.spans.is_empty()
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counters = self
+            .counters
+            .iter()
+            .map(|(name, count)| format!("{}: {}", name, count));
+        let spans = self
+            .spans
+            .iter()
+            .map(|(name, span)| format!("{}: {} in {:?}", name, span.calls, span.total));
+        let all: Vec<String> = counters.chain(spans).collect();
+        f.pad(&all.join(", "))
+    }
+}
+
+thread_local! {
+    static RECORDED: RefCell<Metrics> = RefCell::new(Metrics::default());
+}
+
+/// Adds `n` to the counter called `name`
+#[inline]
+pub fn count(name: &'static str, n: u64) {
+    if ENABLED {
+        RECORDED.with(|m| *m.borrow_mut().counters.entry(name).or_default() += n);
+    }
+}
+
+/// Starts timing a span called `name`, which ends when the returned guard is
+/// dropped
+#[inline]
+pub fn span(name: &'static str) -> Span {
+    Span {
+        started: Some((name, Instant::now())).filter(|_| ENABLED),
+    }
+}
+
+#[must_use = "the span ends as soon as it's dropped"]
+pub struct Span {
+    started: Option<(&'static str, Instant)>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.started {
+            let elapsed = start.elapsed();
+            RECORDED.with(|m| {
+                let mut m = m.borrow_mut();
+                let span = m.spans.entry(name).or_default();
+                span.calls += 1;
+                span.total += elapsed;
+            });
+        }
+    }
+}
+
+/// Runs `f`, collecting everything it records on the current thread. The
+/// metrics are `None` unless the `metrics` feature is enabled
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Metrics>) {
+    RECORDED.with(|m| m.take());
+    let result = f();
+    let metrics = RECORDED.with(|m| m.take());
+    (result, Some(metrics).filter(|_| ENABLED))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_counters_and_spans() {
+        count("before", 1);
+        let (_, metrics) = measure(|| {
+            for _ in 0..3 {
+                let _span = span("step");
+                count("work", 2);
+            }
+            count("other", 1);
+        });
+        assert_eq!(metrics.is_some(), ENABLED);
+        if let Some(metrics) = metrics {
+            assert_eq!(
+                metrics.counters.into_iter().collect::<Vec<_>>(),
+                [("other", 1), ("work", 6)]
+            );
+            assert_eq!(metrics.spans["step"].calls, 3);
+        }
+    }
+
+    #[test]
+    fn lists_everything() {
+        let mut metrics = Metrics::default();
+        metrics.counters.insert("instructions", 12);
+        metrics.spans.insert(
+            "cycle",
+            SpanStats {
+                calls: 6,
+                total: Duration::from_millis(3),
+            },
+        );
+        assert_eq!(metrics.to_string(), "instructions: 12, cycle: 6 in 3ms");
+    }
+}
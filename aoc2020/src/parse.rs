@@ -0,0 +1,149 @@
+use std::{error::Error, fmt, str::FromStr};
+
+/// Returned by a generator when its input is malformed or truncated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-indexed line of the input
+    pub line: usize,
+    /// 1-indexed column of the line, counted in characters
+    pub column: usize,
+    /// The offending text, this is empty if the input ended early
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// `token` should be a slice of `line` so that its column can be worked
+    /// out, if it isn't then the error points to the start of the line
+    pub fn new(
+        day: u8,
+        line_number: usize,
+        line: &str,
+        token: &str,
+        message: impl Into<String>,
+    ) -> Self {
+        let start = line.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|offset| offset + token.len() <= line.len())
+            .unwrap_or(0);
+        Self {
+            day,
+            line: line_number,
+            column: line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// The input stopped at `line_number` while the generator still expected
+    /// more of it
+    pub fn unexpected_end(day: u8, line_number: usize, message: impl Into<String>) -> Self {
+        Self {
+            day,
+            line: line_number,
+            column: 1,
+            text: String::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn from_line(day: u8, line_number: usize, line: &str, error: LineError) -> Self {
+        Self::new(day, line_number, line, error.token, error.message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )?;
+        if self.text.is_empty() {
+            write!(f, " (found end of input)")
+        } else {
+            write!(f, " (found `{}`)", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// An error found while parsing a single line, before it's known where in
+/// the input that line is
+///
+/// `token` should be a slice of the line being parsed, so that the column
+/// can be worked out by [`ParseError::from_line`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError<'a> {
+    pub token: &'a str,
+    pub message: String,
+}
+
+impl<'a> LineError<'a> {
+    pub fn new(token: &'a str, message: impl Into<String>) -> Self {
+        Self {
+            token,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parses every non-blank line of the input with `f`, lines are trimmed
+/// before being passed to it
+pub fn parse_lines<'a, T>(
+    day: u8,
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, LineError<'a>>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| f(line.trim()).map_err(|e| ParseError::from_line(day, i + 1, line, e)))
+        .collect()
+}
+
+pub fn parse_number<T: FromStr>(token: &str) -> Result<T, LineError<'_>> {
+    token
+        .parse()
+        .map_err(|_| LineError::new(token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_column_of_token() {
+        let line = "acc +1 x";
+        let error = ParseError::new(8, 3, line, &line[7..], "unexpected token");
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 8);
+        assert_eq!(error.text, "x");
+        assert_eq!(
+            error.to_string(),
+            "day 8 input, line 3, column 8: unexpected token (found `x`)"
+        );
+    }
+
+    #[test]
+    fn points_to_start_of_line_for_unrelated_token() {
+        let error = ParseError::new(1, 1, "abc", "abc def", "oops");
+        assert_eq!(error.column, 1);
+    }
+
+    #[test]
+    fn parse_lines_skips_blank_lines_and_locates_errors() {
+        let input = "\n1\n  2\n\n  x3";
+        let error = parse_lines::<u32>(1, input, parse_number).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 3));
+        assert_eq!(error.text, "x3");
+        assert_eq!(
+            parse_lines::<u32>(1, "1\n\n2", parse_number).unwrap(),
+            vec![1, 2]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Searching grids for a shape, such as day 20's sea monster
+//!
+//! ```text
+//!                   #
+//! #    ##    ##    ###
+//!  #  #  #  #  #  #
+//! ```
+//!
+//! Spaces are wildcards which match anything, every other character has to
+//! match the cell under it exactly
+
+use crate::grid::{Grid, Orientation, Point};
+use std::{convert::TryFrom, fmt};
+
+/// A shape to look for, `None` cells are wildcards
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Pattern<T> {
+    cells: Grid<Option<T>>,
+}
+
+/// Where a [`Pattern`] was found, `position` is the top left corner of the
+/// pattern once it's in `orientation`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub position: Point,
+    pub orientation: Orientation,
+}
+
+impl<T> Pattern<T>
+where
+    T: TryFrom<char>,
+    T::Error: fmt::Display,
+{
+    /// Parses a template with one character per cell. Lines can be left
+    /// short, the missing cells are wildcards
+    pub fn parse(template: &str) -> Result<Self, String> {
+        let lines: Vec<&str> = template
+            .trim_start_matches('\n')
+            .trim_end_matches('\n')
+            .lines()
+            .collect();
+        let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                let cell = match c {
+                    ' ' => None,
+                    c => Some(
+                        T::try_from(c)
+                            .map_err(|e| format!("line {}, column {}: {}", row + 1, col + 1, e))?,
+                    ),
+                };
+                cells.push(cell);
+            }
+            cells.extend((line.chars().count()..width).map(|_| None));
+        }
+        if cells.iter().all(Option::is_none) {
+            return Err("a pattern needs at least one cell which isn't a wildcard".to_string());
+        }
+        Ok(Self {
+            cells: Grid::new(width, lines.len(), cells),
+        })
+    }
+}
+
+impl<T> Pattern<T>
+where
+    T: PartialEq + Clone,
+{
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Self {
+        Self {
+            cells: self.cells.oriented(orientation),
+        }
+    }
+
+    /// The top left corner of everywhere the pattern matches `grid` as it
+    /// is, row by row. Matches can overlap
+    pub fn find(&self, grid: &Grid<T>) -> Vec<Point> {
+        let (width, height) = (self.width(), self.height());
+        if grid.width() < width || grid.height() < height {
+            return Vec::new();
+        }
+        let cells: Vec<(Point, &T)> = self
+            .cells
+            .points()
+            .filter_map(|(point, cell)| Some((point, cell.as_ref()?)))
+            .collect();
+        let mut found = Vec::new();
+        for top in 0..=grid.height() - height {
+            for left in 0..=grid.width() - width {
+                if cells
+                    .iter()
+                    .all(|((row, col), cell)| grid[(top + row, left + col)] == **cell)
+                {
+                    found.push((top, left));
+                }
+            }
+        }
+        found
+    }
+
+    /// Every match in any of the eight orientations, in the order of
+    /// [`Orientation::ALL`]. Orientations which give the same shape as an
+    /// earlier one aren't searched again, so a symmetric pattern isn't
+    /// matched twice in the same place
+    pub fn find_all(&self, grid: &Grid<T>) -> Vec<Match> {
+        let mut searched: Vec<Self> = Vec::new();
+        let mut found = Vec::new();
+        for orientation in Orientation::all() {
+            let oriented = self.oriented(orientation);
+            if searched.contains(&oriented) {
+                continue;
+            }
+            found.extend(oriented.find(grid).into_iter().map(|position| Match {
+                position,
+                orientation,
+            }));
+            searched.push(oriented);
+        }
+        found
+    }
+
+    /// The points of the grid under the cells of `m` which aren't wildcards
+    pub fn points(&self, m: &Match) -> impl Iterator<Item = Point> + '_ {
+        let Match {
+            position: (top, left),
+            orientation,
+        } = *m;
+        let (width, height) = self.cells.oriented_size(orientation);
+        (0..width * height)
+            .map(move |i| (i / width, i % width))
+            .filter(move |point| {
+                matches!(self.cells.get_oriented(orientation, *point), Some(Some(_)))
+            })
+            .map(move |(row, col)| (top + row, left + col))
+    }
+
+    /// Which cells of `grid` are part of any of `matches`
+    pub fn highlight(&self, grid: &Grid<T>, matches: &[Match]) -> Grid<bool> {
+        let mut highlighted = Grid::filled(grid.width(), grid.height(), false);
+        for point in matches.iter().flat_map(|m| self.points(m)) {
+            highlighted[point] = true;
+        }
+        highlighted
+    }
+
+    /// `grid` with every cell which is part of any of `matches` replaced by
+    /// `with`
+    pub fn mask(&self, grid: &Grid<T>, matches: &[Match], with: T) -> Grid<T> {
+        let mut masked = grid.clone();
+        for point in matches.iter().flat_map(|m| self.points(m)) {
+            masked[point] = with.clone();
+        }
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::parse(0, input).unwrap()
+    }
+
+    #[test]
+    fn parses_wildcards_and_short_lines() {
+        let pattern = Pattern::<char>::parse("\n #\n##.\n").unwrap();
+        assert_eq!((pattern.width(), pattern.height()), (3, 2));
+        assert_eq!(
+            pattern.cells.iter().cloned().collect::<Vec<_>>(),
+            [None, Some('#'), None, Some('#'), Some('#'), Some('.')]
+        );
+        assert!(Pattern::<char>::parse("  \n ").is_err());
+        assert!(Pattern::<char>::parse("").is_err());
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let pattern = Pattern::parse("##").unwrap();
+        let g = grid("###.\n.#..\n.#..");
+        assert_eq!(pattern.find(&g), [(0, 0), (0, 1)]);
+        // Turned half way round it's the same shape, so it isn't counted twice
+        let matches = pattern.find_all(&g);
+        let found: Vec<_> = matches
+            .iter()
+            .map(|m| (m.position, m.orientation))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ((0, 0), Orientation::Identity),
+                ((0, 1), Orientation::Identity),
+                ((0, 1), Orientation::Rotate90),
+                ((1, 1), Orientation::Rotate90),
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_matches_in_every_orientation() {
+        let pattern = Pattern::parse("#.\n #").unwrap();
+        let g = grid("....\n.#..\n##..\n....");
+        let matches = pattern.find_all(&g);
+        assert_eq!(
+            matches,
+            [Match {
+                position: (1, 0),
+                orientation: Orientation::Rotate270
+            }]
+        );
+        assert_eq!(
+            pattern.points(&matches[0]).collect::<Vec<_>>(),
+            [(1, 0), (1, 1), (2, 0)]
+        );
+    }
+
+    #[test]
+    fn masks_and_highlights_matches() {
+        let pattern = Pattern::parse("# #").unwrap();
+        let g = grid("#.#.#\n.....");
+        let matches = pattern.find_all(&g);
+        assert_eq!(matches.len(), 2);
+        assert_eq!(pattern.mask(&g, &matches, 'O').to_string(), "O.O.O\n.....");
+        let highlighted = pattern.highlight(&g, &matches);
+        assert_eq!(highlighted.iter().filter(|h| **h).count(), 3);
+        assert!(!highlighted[(0, 1)]);
+    }
+
+    #[test]
+    #[cfg(feature = "day11")]
+    fn searches_seat_layouts() {
+        use crate::day_11::{generator, Layout};
+
+        let layout = generator("L.LL\n#LL#\nL.#L").unwrap();
+        let pattern = Pattern::<Layout>::parse("L L").unwrap();
+        assert_eq!(pattern.find(&layout), [(0, 0)]);
+        let matches = pattern.find_all(&layout);
+        assert_eq!(matches.len(), 3);
+        assert_eq!(
+            pattern.mask(&layout, &matches, Layout::Floor).to_string(),
+            "....\n#LL#\n..#."
+        );
+        assert!(Pattern::<Layout>::parse("L?").is_err());
+    }
+}
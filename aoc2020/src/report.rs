@@ -0,0 +1,426 @@
+use crate::{
+    answers::Answers,
+    bench::{self, Phase},
+    input,
+    runner::{Outcome, Status},
+    solution::Part,
+};
+use std::{collections::BTreeMap, fmt, fs, io, path::Path, time::Duration};
+
+/// Where the `day_N.rs` modules are read from
+pub const SOURCE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// The generated table in the readme goes between these
+pub const README_START: &str = "<!-- progress:start -->";
+pub const README_END: &str = "<!-- progress:end -->";
+
+const LAST_DAY: u8 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Implementation {
+    /// There's no `#[aoc]` function for the part
+    Missing,
+    /// The part's `#[aoc]` function still has a `todo!()` in it
+    Unfinished,
+    Implemented,
+}
+
+/// What a day's module says about each part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Source {
+    pub parts: [Implementation; 2],
+    /// Whether `aoc_test!` checks the part against the real input
+    pub real_tests: [bool; 2],
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Self {
+            parts: [Implementation::Missing; 2],
+            real_tests: [false; 2],
+        }
+    }
+}
+
+impl Source {
+    pub fn parse(source: &str) -> Self {
+        let mut parsed = Self::default();
+        for (start, _) in source.match_indices("#[aoc(") {
+            let rest = &source[start..];
+            let end = match rest.find(")]") {
+                Some(end) => end,
+                None => continue,
+            };
+            // i.e. `day20, part1`, with an optional name after the part
+            let part = match rest["#[aoc(".len()..end].split(',').nth(1).map(str::trim) {
+                Some("part1") => 0,
+                Some("part2") => 1,
+                _ => continue,
+            };
+            let rest = &rest[end..];
+            let body = function_body(rest).unwrap_or(rest);
+            parsed.parts[part] = if body.contains("todo!(") || body.contains("unimplemented!(") {
+                Implementation::Unfinished
+            } else {
+                Implementation::Implemented
+            };
+        }
+        if let Some(answers) = real_answers(source) {
+            for (i, answer) in answers.split(',').take(2).enumerate() {
+                let answer = answer.trim();
+                parsed.real_tests[i] = !answer.is_empty() && answer != "_";
+            }
+        }
+        parsed
+    }
+}
+
+/// What's between the brackets of `real => [..]` in an `aoc_test!`
+fn real_answers(source: &str) -> Option<&str> {
+    source.match_indices("real").find_map(|(i, _)| {
+        let rest = source[i + "real".len()..].trim_start().strip_prefix("=>")?;
+        let rest = rest.trim_start().strip_prefix('[')?;
+        rest.find(']').map(|end| &rest[..end])
+    })
+}
+
+/// The body of the first function in `source`, found by matching braces.
+/// Braces in string and character literals are skipped
+fn function_body(source: &str) -> Option<&str> {
+    let start = source.find('{')?;
+    let mut depth = 0;
+    let mut chars = source[start..].char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            // A character literal, rather than a lifetime
+            '\'' if source[start + i..].chars().nth(2) == Some('\'') => {
+                chars.next();
+                chars.next();
+            }
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&source[start..=start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Reads every `day_N.rs` module in `dir`, days without one are left out
+pub fn scan(dir: &Path) -> io::Result<BTreeMap<u8, Source>> {
+    let mut sources = BTreeMap::new();
+    for day in 1..=LAST_DAY {
+        match fs::read_to_string(dir.join(format!("day_{}.rs", day))) {
+            Ok(source) => {
+                sources.insert(day, Source::parse(&source));
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(sources)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartProgress {
+    pub implementation: Implementation,
+    /// Checked against the real input, either by `aoc_test!` or by
+    /// `crate::answers` with its recorded answer
+    pub real_test: bool,
+    /// Gave the recorded answer when it was run
+    pub verified: bool,
+    pub best: Option<Duration>,
+}
+
+impl fmt::Display for PartProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match (self.implementation, self.verified) {
+            (Implementation::Implemented, true) => "verified",
+            (Implementation::Implemented, false) => "implemented",
+            (Implementation::Unfinished, _) => "unfinished",
+            (Implementation::Missing, _) => "missing",
+        };
+        f.pad(status)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: u8,
+    pub has_input: bool,
+    pub parts: [PartProgress; 2],
+}
+
+/// Where each day of a year stands
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub year: u16,
+    pub days: Vec<DayProgress>,
+}
+
+impl Progress {
+    /// Combines what the `sources` say with the recorded `answers` and the
+    /// `outcomes` of running every day, the best times are taken from the
+    /// `bench` report if there is one
+    pub fn new(
+        year: u16,
+        sources: &BTreeMap<u8, Source>,
+        answers: &Answers,
+        outcomes: &[Outcome],
+        bench: Option<&bench::Report>,
+        input_dir: &Path,
+    ) -> Self {
+        let days = (1..=LAST_DAY)
+            .map(|day| {
+                let source = sources.get(&day).copied().unwrap_or_default();
+                let part = |i: usize, part: Part| {
+                    let outcome = outcomes.iter().find(|o| o.day == day && o.part == part);
+                    let measured = bench.and_then(|b| b.get(day, Phase::from(part)));
+                    PartProgress {
+                        implementation: source.parts[i],
+                        real_test: source.real_tests[i] || answers.get(day, part).is_some(),
+                        verified: outcome.is_some_and(|o| o.status == Status::Pass),
+                        best: measured
+                            .map(|m| m.min)
+                            .or_else(|| outcome.filter(|o| o.answer.is_some()).map(|o| o.elapsed)),
+                    }
+                };
+                DayProgress {
+                    day,
+                    has_input: input::input_path(input_dir, year, day).exists(),
+                    parts: [part(0, Part::One), part(1, Part::Two)],
+                }
+            })
+            .collect();
+        Self { year, days }
+    }
+
+    /// Parts which gave their recorded answer
+    pub fn stars(&self) -> usize {
+        self.days
+            .iter()
+            .flat_map(|d| d.parts.iter())
+            .filter(|p| p.verified)
+            .count()
+    }
+
+    /// A markdown table with a row per day, days without a module or an
+    /// input are left out after the last one which has either
+    pub fn to_markdown(&self) -> String {
+        let last = self
+            .days
+            .iter()
+            .rev()
+            .find(|d| d.has_input || d.parts[0].implementation != Implementation::Missing)
+            .map_or(0, |d| d.day);
+        let rows: Vec<[String; 5]> = self
+            .days
+            .iter()
+            .filter(|d| d.day <= last)
+            .map(|d| {
+                let tested: Vec<String> = d
+                    .parts
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.real_test)
+                    .map(|(i, _)| (i + 1).to_string())
+                    .collect();
+                let times: Vec<String> = d
+                    .parts
+                    .iter()
+                    .map(|p| p.best.map_or("-".to_string(), |t| format!("{:.2?}", t)))
+                    .collect();
+                [
+                    d.day.to_string(),
+                    d.parts[0].to_string(),
+                    d.parts[1].to_string(),
+                    if tested.is_empty() {
+                        "-".to_string()
+                    } else {
+                        tested.join(", ")
+                    },
+                    times.join(" / "),
+                ]
+            })
+            .collect();
+
+        let header =
+            ["Day", "Part 1", "Part 2", "Real input tests", "Best times"].map(str::to_string);
+        let mut widths = header.clone().map(|h| h.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+        let line = |row: &[String; 5]| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<w$}", cell, w = width))
+                .collect();
+            format!("| {} |\n", cells.join(" | "))
+        };
+
+        let mut table = format!("{} / {} stars\n\n", self.stars(), usize::from(LAST_DAY) * 2);
+        table.push_str(&line(&header));
+        let divider: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        table.push_str(&format!("| {} |\n", divider.join(" | ")));
+        for row in &rows {
+            table.push_str(&line(row));
+        }
+        table
+    }
+}
+
+/// Replaces whatever is between [`README_START`] and [`README_END`] in
+/// `readme` with `table`
+pub fn update_readme(readme: &str, table: &str) -> Result<String, String> {
+    let start = readme
+        .find(README_START)
+        .ok_or_else(|| format!("`{}` is missing", README_START))?
+        + README_START.len();
+    let end = readme[start..]
+        .find(README_END)
+        .ok_or_else(|| format!("`{}` is missing", README_END))?
+        + start;
+    Ok(format!(
+        "{}\n\n{}\n{}",
+        &readme[..start],
+        table.trim_end(),
+        &readme[end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+    use std::path::PathBuf;
+
+    const SOURCE: &str = r#"
+#[aoc(day20, part1)]
+pub fn puzzle_1(input: &str) -> usize {
+    let brace = '}';
+    let s = "}}";
+    input.len()
+}
+
+#[aoc(day20, part2)]
+pub fn puzzle_2<'a>(input: &'a str) -> usize {
+    if input.is_empty() {
+        return 0;
+    }
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    aoc_test! {
+        Day20,
+        real => [29125888761511, _],
+    }
+}
+"#;
+
+    #[test]
+    fn finds_unfinished_parts_and_real_tests() {
+        let source = Source::parse(SOURCE);
+        assert_eq!(
+            source.parts,
+            [Implementation::Implemented, Implementation::Unfinished]
+        );
+        assert_eq!(source.real_tests, [true, false]);
+        assert_eq!(Source::parse(""), Source::default());
+
+        // A recorded answer is checked against the real input by `answers`
+        let mut sources = BTreeMap::new();
+        sources.insert(1, Source::parse(""));
+        let answers = Answers::parse("[day1]\npart2 = 1\n").unwrap();
+        let progress = Progress::new(
+            2020,
+            &sources,
+            &answers,
+            &[],
+            None,
+            &PathBuf::from("/nonexistent"),
+        );
+        assert_eq!(progress.days[0].parts.map(|p| p.real_test), [false, true]);
+    }
+
+    #[test]
+    fn matches_braces_of_the_function() {
+        let body = function_body("fn a() { let b = '{'; { \"}\" } } fn c() {}").unwrap();
+        assert_eq!(body, "{ let b = '{'; { \"}\" } }");
+    }
+
+    #[test]
+    fn scans_this_crate() {
+        let sources = scan(Path::new(SOURCE_DIR)).unwrap();
+        assert_eq!(
+            sources[&1].parts,
+            [Implementation::Implemented, Implementation::Implemented]
+        );
+        assert!(!sources.contains_key(&25));
+    }
+
+    #[test]
+    fn renders_a_table_and_updates_the_readme() {
+        let mut sources = BTreeMap::new();
+        sources.insert(1, Source::parse(SOURCE));
+        let outcomes = vec![Outcome {
+            day: 1,
+            part: Part::One,
+            answer: Some(Answer::from(1)),
+            status: Status::Pass,
+            elapsed: Duration::from_micros(1500),
+            allocations: None,
+            metrics: None,
+        }];
+        let progress = Progress::new(
+            2020,
+            &sources,
+            &Answers::default(),
+            &outcomes,
+            None,
+            &PathBuf::from("/nonexistent"),
+        );
+        assert_eq!(progress.stars(), 1);
+        let table = progress.to_markdown();
+        assert_eq!(
+            table,
+            "1 / 50 stars
+
+| Day | Part 1   | Part 2     | Real input tests | Best times |
+| --- | -------- | ---------- | ---------------- | ---------- |
+| 1   | verified | unfinished | 1                | 1.50ms / - |
+"
+        );
+
+        let readme = format!("# Title\n{}\nold\n{}\nrest\n", README_START, README_END);
+        assert_eq!(
+            update_readme(&readme, &table).unwrap(),
+            format!(
+                "# Title\n{}\n\n{}\n{}\nrest\n",
+                README_START,
+                table.trim_end(),
+                README_END
+            )
+        );
+        assert!(update_readme("# Title", &table).is_err());
+    }
+}
@@ -0,0 +1,506 @@
+use crate::{
+    answers::Answers,
+    config::Overrides,
+    input::{self, InputError, NamedInputs},
+    memory::{self, Allocations},
+    metrics::{self, Metrics},
+    solution::{Part, Solver},
+    Answer,
+};
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How a single part of a day went when it was run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: Answer,
+    },
+    /// There's no recorded answer to check against
+    Unchecked,
+    /// The input couldn't be loaded or parsed, the config was invalid, or the
+    /// part panicked
+    Error(String),
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => f.pad("pass"),
+            Status::Fail { expected } if expected.is_multi_line() => f.pad("FAIL"),
+            Status::Fail { expected } => f.pad(&format!("FAIL (expected {})", expected)),
+            Status::Unchecked => f.pad("unchecked"),
+            Status::Error(message) => f.pad(&format!("ERROR ({})", message)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: Part,
+    /// `None` if the part couldn't be run
+    pub answer: Option<Answer>,
+    pub status: Status,
+    /// How long the part took to solve, not including parsing the input
+    pub elapsed: Duration,
+    /// What solving the part allocated, with the `alloc-stats` feature
+    pub allocations: Option<Allocations>,
+    /// What solving the part recorded, with the `metrics` feature
+    pub metrics: Option<Metrics>,
+}
+
+struct Job {
+    solution: &'static dyn Solver,
+    part: Part,
+    input: Arc<String>,
+}
+
+/// Runs both parts of every one of the `solutions` on a pool of `threads`
+/// worker threads, checking each answer against the recorded `answers`
+///
+/// Each part parses its own copy of the input, so the parts of a day can run
+/// at the same time. A part which panics is reported as an [`Status::Error`]
+/// without stopping the rest. Timings are only comparable with a single thread,
+/// as the parts compete for cores otherwise
+///
+/// The outcomes are ordered by day, then part
+pub fn run_all(
+    solutions: &[&'static dyn Solver],
+    dir: &Path,
+    overrides: &Overrides,
+    answers: &Answers,
+    threads: usize,
+) -> Vec<Outcome> {
+    run(
+        solutions,
+        |solution| input::load_input_from(dir, solution.year(), solution.day()),
+        overrides,
+        answers,
+        threads,
+    )
+}
+
+/// Like [`run_all`], but against someone's [`NamedInputs`] rather than the
+/// default ones. Days they don't have an input for are left out
+pub fn run_named(
+    solutions: &[&'static dyn Solver],
+    inputs: &NamedInputs,
+    overrides: &Overrides,
+    answers: &Answers,
+    threads: usize,
+) -> Vec<Outcome> {
+    let solutions: Vec<_> = solutions
+        .iter()
+        .copied()
+        .filter(|s| inputs.has(s.day()))
+        .collect();
+    run(
+        &solutions,
+        |solution| inputs.load(solution.day()),
+        overrides,
+        answers,
+        threads,
+    )
+}
+
+fn run(
+    solutions: &[&'static dyn Solver],
+    load: impl Fn(&dyn Solver) -> Result<String, InputError>,
+    overrides: &Overrides,
+    answers: &Answers,
+    threads: usize,
+) -> Vec<Outcome> {
+    let mut outcomes = Vec::with_capacity(solutions.len() * Part::ALL.len());
+    let (job_tx, job_rx) = mpsc::channel();
+    for solution in solutions {
+        match load(*solution) {
+            Ok(input) => {
+                let input = Arc::new(input);
+                for part in Part::ALL.iter() {
+                    let job = Job {
+                        solution: *solution,
+                        part: *part,
+                        input: Arc::clone(&input),
+                    };
+                    job_tx.send(job).expect("receiver is still in scope");
+                }
+            }
+            Err(e) => outcomes.extend(Part::ALL.iter().map(|part| Outcome {
+                day: solution.day(),
+                part: *part,
+                answer: None,
+                status: Status::Error(e.to_string()),
+                elapsed: Duration::default(),
+                allocations: None,
+                metrics: None,
+            })),
+        }
+    }
+    drop(job_tx);
+
+    let job_rx = Arc::new(Mutex::new(job_rx));
+    let overrides = Arc::new(overrides.clone());
+    let (tx, rx) = mpsc::channel();
+    let mut handles = Vec::with_capacity(threads);
+    for _ in 0..threads.max(1) {
+        let tx_clone = tx.clone();
+        let job_rx = Arc::clone(&job_rx);
+        let overrides = Arc::clone(&overrides);
+
+        handles.push(thread::spawn(move || loop {
+            // The lock is released as soon as the job has been taken
+            let job = job_rx.lock().expect("no job panics while locked").recv();
+            match job {
+                Ok(job) => tx_clone.send(run_job(&job, &overrides)).unwrap(),
+                Err(_) => break,
+            }
+        }));
+    }
+
+    drop(tx);
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    outcomes.extend(rx.into_iter().map(|(day, part, result, elapsed)| {
+        let (answer, status, allocations, metrics) = match result {
+            Ok(Solved {
+                answer,
+                allocations,
+                metrics,
+            }) => {
+                let status = match answers.get(day, part) {
+                    Some(expected) if *expected == answer => Status::Pass,
+                    Some(expected) => Status::Fail {
+                        expected: expected.clone(),
+                    },
+                    None => Status::Unchecked,
+                };
+                (Some(answer), status, allocations, metrics)
+            }
+            Err(message) => (None, Status::Error(message), None, None),
+        };
+        Outcome {
+            day,
+            part,
+            answer,
+            status,
+            elapsed,
+            allocations,
+            metrics,
+        }
+    }));
+    outcomes.sort_by_key(|o| (o.day, o.part));
+    outcomes
+}
+
+#[derive(Debug)]
+struct Solved {
+    answer: Answer,
+    allocations: Option<Allocations>,
+    metrics: Option<Metrics>,
+}
+
+fn run_job(job: &Job, overrides: &Overrides) -> (u8, Part, Result<Solved, String>, Duration) {
+    let solution = job.solution;
+    let mut elapsed = Duration::default();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let parsed = solution.parse(&job.input).map_err(|e| e.to_string())?;
+        let config = overrides.config_for(solution)?;
+        let start = Instant::now();
+        let ((answer, allocations), metrics) = metrics::measure(|| {
+            memory::measure(|| solution.solve(job.part, parsed.as_ref(), config.as_ref()))
+        });
+        elapsed = start.elapsed();
+        Ok(Solved {
+            answer,
+            allocations,
+            metrics,
+        })
+    }))
+    .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload.as_ref()))));
+    (solution.day(), job.part, result, elapsed)
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+/// The metrics each part recorded as a JSON array, parts which didn't record
+/// any are left out
+pub fn metrics_json(outcomes: &[Outcome]) -> String {
+    let recorded: Vec<_> = outcomes
+        .iter()
+        .filter_map(|o| {
+            let metrics = o.metrics.as_ref().filter(|m| !m.is_empty())?;
+            Some(serde_json::json!({
+                "day": o.day,
+                "part": o.part.to_string(),
+                "counters": metrics.counters,
+                "spans": metrics.spans,
+            }))
+        })
+        .collect();
+    serde_json::to_string_pretty(&recorded).expect("metrics always serialize")
+}
+
+/// Lays the `outcomes` out as a table with a row for each part, with a
+/// column for their allocations if any were counted
+pub fn table(outcomes: &[Outcome]) -> String {
+    let counted = outcomes.iter().any(|o| o.allocations.is_some());
+    let rows: Vec<[String; 5]> = outcomes
+        .iter()
+        .map(|o| {
+            let answer = match &o.answer {
+                // Pictures don't fit in a table
+                Some(answer) if answer.is_multi_line() => {
+                    format!("<{} lines>", answer.to_string().trim().lines().count())
+                }
+                Some(answer) => answer.to_string(),
+                None => "-".to_string(),
+            };
+            [
+                o.day.to_string(),
+                o.part.to_string(),
+                answer,
+                o.status.to_string(),
+                format!("{:?}", o.elapsed),
+            ]
+        })
+        .collect();
+    let header = ["Day", "Part", "Answer", "Status", "Time"];
+    let mut widths = header.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let header = header.map(str::to_string);
+    let allocations = outcomes.iter().map(|o| match o.allocations {
+        Some(allocations) => allocations.to_string(),
+        None => "-".to_string(),
+    });
+    let allocations = std::iter::once("Allocations".to_string()).chain(allocations);
+    for (row, allocations) in std::iter::once(&header).chain(&rows).zip(allocations) {
+        let mut line = format!(
+            "{:>w0$} | {:>w1$} | {:<w2$} | {:<w3$} | {:>w4$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        if counted {
+            line.push_str(&format!(" | {}", allocations));
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse::ParseError, solution::Solution};
+
+    #[test]
+    #[cfg(all(feature = "day1", feature = "day2", feature = "day6"))]
+    fn runs_and_checks_every_part() {
+        use crate::{find_solution, temp_dir::TempDir, YEAR};
+        use std::fs;
+
+        let dir = TempDir::new("runner");
+        fs::create_dir_all(dir.join(YEAR.to_string())).unwrap();
+        fs::write(
+            input::input_path(&dir, YEAR, 1),
+            "1721\n979\n366\n299\n675\n1456",
+        )
+        .unwrap();
+        fs::write(input::input_path(&dir, YEAR, 6), "").unwrap();
+        let solutions = vec![
+            find_solution(YEAR, 6).unwrap(),
+            find_solution(YEAR, 1).unwrap(),
+            find_solution(YEAR, 2).unwrap(),
+        ];
+        let answers = Answers::parse("[day1]\npart1 = 514579\npart2 = 1\n").unwrap();
+
+        for threads in [1, 4].iter() {
+            let outcomes = run_all(&solutions, &dir, &Overrides::default(), &answers, *threads);
+            let summary: Vec<_> = outcomes
+                .iter()
+                .map(|o| (o.day, o.part, o.answer.clone(), o.status.clone()))
+                .collect();
+            assert_eq!(summary.len(), 6);
+            assert_eq!(
+                summary[..2],
+                [
+                    (1, Part::One, Some(Answer::from(514579)), Status::Pass),
+                    (
+                        1,
+                        Part::Two,
+                        Some(Answer::from(241861950)),
+                        Status::Fail {
+                            expected: Answer::from(1)
+                        }
+                    ),
+                ]
+            );
+            assert!(summary[2..4]
+                .iter()
+                .all(|(day, _, answer, status)| *day == 2
+                    && answer.is_none()
+                    && matches!(status, Status::Error(e) if e.starts_with("failed to read input"))));
+            assert_eq!(summary[4].3, Status::Unchecked);
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "day1", feature = "day6"))]
+    fn runs_named_inputs_they_have() {
+        use crate::{find_solution, temp_dir::TempDir, YEAR};
+        use std::fs;
+
+        let dir = TempDir::new("runner-named");
+        let inputs = NamedInputs::new(&dir, YEAR, "alice");
+        fs::create_dir_all(&inputs.dir).unwrap();
+        fs::write(inputs.input_path(1), "1721\n979\n366\n299\n675\n1456").unwrap();
+        fs::write(inputs.answers_path(), "[day1]\npart1 = 514579\n").unwrap();
+        let solutions = vec![
+            find_solution(YEAR, 1).unwrap(),
+            find_solution(YEAR, 6).unwrap(),
+        ];
+        let answers = Answers::load_named(&inputs).unwrap();
+
+        let outcomes = run_named(&solutions, &inputs, &Overrides::default(), &answers, 2);
+        let statuses: Vec<_> = outcomes
+            .iter()
+            .map(|o| (o.day, o.part, o.status.clone()))
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                (1, Part::One, Status::Pass),
+                (1, Part::Two, Status::Unchecked)
+            ]
+        );
+    }
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const DAY: u8 = 1;
+        type Input = ();
+        type Config = ();
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part_1(_: &(), _: &()) -> Answer {
+            panic!("part 1")
+        }
+
+        fn part_2(_: &(), _: &()) -> Answer {
+            Answer::from(2)
+        }
+    }
+
+    #[test]
+    fn panics_are_isolated() {
+        let job = Job {
+            solution: &Panics,
+            part: Part::One,
+            input: Arc::new(String::new()),
+        };
+        let (_, _, result, _) = run_job(&job, &Overrides::default());
+        assert!(result.unwrap_err().starts_with("panicked: "));
+    }
+
+    #[test]
+    fn lays_out_a_table() {
+        let outcomes = vec![
+            Outcome {
+                day: 1,
+                part: Part::One,
+                answer: Some(Answer::from(514579)),
+                status: Status::Pass,
+                elapsed: Duration::from_millis(2),
+                allocations: None,
+                metrics: None,
+            },
+            Outcome {
+                day: 20,
+                part: Part::Two,
+                answer: Some(Answer::parse("#.\n.#")),
+                status: Status::Unchecked,
+                elapsed: Duration::from_millis(10),
+                allocations: Some(Allocations {
+                    count: 2,
+                    bytes: 2048,
+                    peak: 1024,
+                }),
+                metrics: None,
+            },
+        ];
+        assert_eq!(
+            table(&outcomes),
+            "Day | Part | Answer    | Status    | Time | Allocations
+  1 |    1 | 514579    | pass      |  2ms | -
+ 20 |    2 | <2 lines> | unchecked | 10ms | 2 allocs, 2.0 KiB total, 1.0 KiB peak
+"
+        );
+    }
+
+    #[test]
+    fn dumps_recorded_metrics() {
+        let mut metrics = Metrics::default();
+        metrics.counters.insert("instructions", 5730);
+        let outcome = |part, metrics| Outcome {
+            day: 8,
+            part,
+            answer: Some(Answer::from(1)),
+            status: Status::Unchecked,
+            elapsed: Duration::default(),
+            allocations: None,
+            metrics,
+        };
+        let outcomes = vec![
+            outcome(Part::One, Some(Metrics::default())),
+            outcome(Part::Two, Some(metrics)),
+        ];
+        let json: serde_json::Value = serde_json::from_str(&metrics_json(&outcomes)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{
+                "day": 8,
+                "part": "2",
+                "counters": { "instructions": 5730 },
+                "spans": {},
+            }])
+        );
+    }
+}
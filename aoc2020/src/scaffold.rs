@@ -0,0 +1,345 @@
+//! Adds a new day to the crate, everything which has to be wired up by hand
+//! otherwise
+//!
+//! - `src/day_N.rs`, with a generator, both parts and tests to fill in
+//! - `pub mod day_N;` in `src/lib.rs`, and its entry in [`SOLUTIONS`]
+//! - a `dayN` feature in `Cargo.toml`, enabled by `all`
+//! - an empty input, unless there's one already
+//!
+//! [`SOLUTIONS`]: crate::SOLUTIONS
+
+use crate::input;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The crate's own directory, where [`scaffold`] adds days to
+pub const CRATE_DIR: &str = env!("CARGO_MANIFEST_DIR");
+
+const LAST_DAY: u8 = 25;
+
+/// The module for a new `day`, both parts are left as `todo!()`
+pub fn module(day: u8) -> String {
+    format!(
+        r#"use crate::{{
+    parse::{{parse_lines, ParseError}},
+    solution::Solution,
+    Answer,
+}};
+
+#[aoc_generator(day{day})]
+pub fn generator(input: &str) -> Result<Vec<String>, ParseError> {{
+    parse_lines({day}, input, |line| Ok(line.to_string()))
+}}
+
+#[aoc(day{day}, part1)]
+pub fn puzzle_1(_input: &[String]) -> usize {{
+    todo!()
+}}
+
+#[aoc(day{day}, part2)]
+pub fn puzzle_2(_input: &[String]) -> usize {{
+    todo!()
+}}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Config = ();
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        generator(input)
+    }}
+
+    fn part_1(input: &Self::Input, _: &()) -> Answer {{
+        puzzle_1(input).into()
+    }}
+
+    fn part_2(input: &Self::Input, _: &()) -> Answer {{
+        puzzle_2(input).into()
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const TEST_INPUT: &str = "";
+
+    aoc_test! {{
+        Day{day},
+        example: TEST_INPUT => [_, _],
+        real => [_, _],
+    }}
+}}
+"#,
+        day = day
+    )
+}
+
+/// Adds the module and its entry in `SOLUTIONS` to `lib_rs`, keeping the
+/// modules sorted by name and the solutions by day
+pub fn register_module(lib_rs: &str, day: u8) -> Result<String, String> {
+    let name = format!("day_{}", day);
+    let mut lines: Vec<String> = lib_rs.lines().map(str::to_string).collect();
+    let module = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|l| l.strip_suffix(';'))
+            .map(str::to_string)
+    };
+    if lines
+        .iter()
+        .any(|l| module(l).as_deref() == Some(name.as_str()))
+    {
+        return Err(format!("`{}` is already declared", name));
+    }
+
+    let last_module = lines
+        .iter()
+        .rposition(|l| module(l).is_some())
+        .ok_or("no modules are declared")?;
+    let at = lines
+        .iter()
+        .position(|l| module(l).is_some_and(|m| m > name))
+        .map_or(last_module + 1, |i| attribute_start(&lines, i));
+    lines.splice(
+        at..at,
+        vec![
+            format!("#[cfg(feature = \"day{}\")]", day),
+            format!("pub mod {};", name),
+        ],
+    );
+
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub static SOLUTIONS"))
+        .ok_or("`SOLUTIONS` is missing")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l == "];")
+            .ok_or("`SOLUTIONS` isn't closed")?;
+    let registered_day = |line: &str| {
+        line.trim()
+            .strip_prefix("&day_")
+            .and_then(|l| l.split("::").next())
+            .and_then(|d| d.parse::<u8>().ok())
+    };
+    let at = (start..end)
+        .find(|i| registered_day(&lines[*i]).is_some_and(|d| d > day))
+        .map_or(end, |i| attribute_start(&lines, i));
+    lines.splice(
+        at..at,
+        vec![
+            format!("    #[cfg(feature = \"day{}\")]", day),
+            format!("    &{}::Day{},", name, day),
+        ],
+    );
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The line an item starts on, including any attributes above `line`
+fn attribute_start(lines: &[String], mut line: usize) -> usize {
+    while line > 0 && lines[line - 1].trim_start().starts_with("#[") {
+        line -= 1;
+    }
+    line
+}
+
+/// Adds a `dayN` feature to `cargo_toml`, after the other days, and enables
+/// it in `all`
+pub fn register_feature(cargo_toml: &str, day: u8) -> Result<String, String> {
+    let feature = format!("day{}", day);
+    let mut lines: Vec<String> = cargo_toml.lines().map(str::to_string).collect();
+    let is_day_feature = |line: &str| {
+        line.split(" = ")
+            .next()
+            .and_then(|name| name.strip_prefix("day"))
+            .is_some_and(|d| d.parse::<u8>().is_ok())
+    };
+    if lines
+        .iter()
+        .any(|l| l.split(" = ").next() == Some(feature.as_str()))
+    {
+        return Err(format!("the `{}` feature already exists", feature));
+    }
+
+    let last = lines
+        .iter()
+        .rposition(|l| is_day_feature(l))
+        .ok_or("there are no day features")?;
+    lines.insert(last + 1, format!("{} = []", feature));
+
+    let all = lines
+        .iter()
+        .position(|l| l.starts_with("all = ["))
+        .ok_or("the `all` feature is missing")?;
+    let end = all
+        + lines[all..]
+            .iter()
+            .position(|l| l == "]")
+            .ok_or("the `all` feature isn't closed")?;
+    lines.insert(end, format!("    \"{}\",", feature));
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `day` to the crate in `crate_dir`, with its input in `input_dir`.
+/// Nothing is changed if the day already exists
+///
+/// Returns the files which were created or changed
+pub fn scaffold(
+    crate_dir: &Path,
+    input_dir: &Path,
+    year: u16,
+    day: u8,
+) -> Result<Vec<PathBuf>, String> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(format!("there's no day {}", day));
+    }
+    let module_path = crate_dir.join("src").join(format!("day_{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("`{}` already exists", module_path.display()));
+    }
+    let lib_path = crate_dir.join("src").join("lib.rs");
+    let manifest_path = crate_dir.join("Cargo.toml");
+    // Everything is checked before anything is written
+    let lib_rs = register_module(&read(&lib_path)?, day)?;
+    let cargo_toml = register_feature(&read(&manifest_path)?, day)?;
+
+    write(&module_path, &module(day))?;
+    write(&lib_path, &lib_rs)?;
+    write(&manifest_path, &cargo_toml)?;
+    let mut changed = vec![module_path, lib_path, manifest_path];
+
+    let input_path = input::input_path(input_dir, year, day);
+    if !input_path.exists() {
+        if let Some(parent) = input_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create `{}`: {}", parent.display(), e))?;
+        }
+        write(&input_path, "")?;
+        changed.push(input_path);
+    }
+    Ok(changed)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read `{}`: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("failed to write `{}`: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir::TempDir;
+
+    const LIB_RS: &str = "pub mod answer;
+#[cfg(feature = \"day1\")]
+pub mod day_1;
+#[cfg(feature = \"day2\")]
+pub mod day_2;
+pub mod gen;
+
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    #[cfg(feature = \"day1\")]
+    &day_1::Day1,
+    #[cfg(feature = \"day2\")]
+    &day_2::Day2,
+];
+";
+
+    const CARGO_TOML: &str = "[features]
+default = [\"all\"]
+all = [
+    \"day1\",
+    \"day2\",
+]
+# One per day
+day1 = []
+day2 = [\"regex\"]
+";
+
+    #[test]
+    fn registers_modules_in_order() {
+        let lib_rs = register_module(LIB_RS, 10).unwrap();
+        assert_eq!(
+            lib_rs,
+            "pub mod answer;
+#[cfg(feature = \"day1\")]
+pub mod day_1;
+#[cfg(feature = \"day10\")]
+pub mod day_10;
+#[cfg(feature = \"day2\")]
+pub mod day_2;
+pub mod gen;
+
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    #[cfg(feature = \"day1\")]
+    &day_1::Day1,
+    #[cfg(feature = \"day2\")]
+    &day_2::Day2,
+    #[cfg(feature = \"day10\")]
+    &day_10::Day10,
+];
+"
+        );
+        assert!(register_module(LIB_RS, 2).is_err());
+    }
+
+    #[test]
+    fn registers_features() {
+        assert_eq!(
+            register_feature(CARGO_TOML, 3).unwrap(),
+            "[features]
+default = [\"all\"]
+all = [
+    \"day1\",
+    \"day2\",
+    \"day3\",
+]
+# One per day
+day1 = []
+day2 = [\"regex\"]
+day3 = []
+"
+        );
+        assert!(register_feature(CARGO_TOML, 1).is_err());
+    }
+
+    #[test]
+    fn scaffolds_a_day_once() {
+        let dir = TempDir::new("scaffold");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/lib.rs"), LIB_RS).unwrap();
+        fs::write(dir.join("Cargo.toml"), CARGO_TOML).unwrap();
+        let input_dir = dir.join("input");
+
+        let changed = scaffold(&dir, &input_dir, 2020, 3).unwrap();
+        assert_eq!(changed.len(), 4);
+        assert_eq!(
+            fs::read_to_string(dir.join("src/day_3.rs")).unwrap(),
+            module(3)
+        );
+        assert_eq!(
+            fs::read_to_string(input::input_path(&input_dir, 2020, 3)).unwrap(),
+            ""
+        );
+        assert!(fs::read_to_string(dir.join("src/lib.rs"))
+            .unwrap()
+            .contains("pub mod day_3;"));
+
+        let lib_rs = fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(scaffold(&dir, &input_dir, 2020, 3).is_err());
+        // Already declared, without a module
+        assert!(scaffold(&dir, &input_dir, 2020, 2).is_err());
+        assert!(!dir.join("src/day_2.rs").exists());
+        assert!(scaffold(&dir, &input_dir, 2020, 26).is_err());
+        assert_eq!(fs::read_to_string(dir.join("src/lib.rs")).unwrap(), lib_rs);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{parse::ParseError, Answer};
+use serde::de::{DeserializeOwned, Error as _};
+use std::any::{Any, TypeId};
+use std::fmt;
+use toml::{value::Table, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Name given to [`Solution::part_1`] and [`Solution::part_2`] when they're
+/// listed alongside a part's alternatives
+pub const MAIN: &str = "main";
+
+/// Another way of solving one part of a day, which should always give the same
+/// answer as the main implementation
+pub struct Alternative<I: 'static, C: 'static> {
+    pub name: &'static str,
+    pub part: Part,
+    pub solve: fn(&I, &C) -> Answer,
+}
+
+/// A day's puzzle, wrapping its `#[aoc_generator]` and `#[aoc]` functions
+/// behind a uniform interface
+pub trait Solution {
+    const YEAR: u16 = crate::YEAR;
+    const DAY: u8;
+
+    /// The output of the day's generator
+    type Input: 'static;
+
+    /// Parameters of the puzzle which can be changed to answer variations of
+    /// it, `()` if there aren't any
+    ///
+    /// The default is the puzzle as it was set, any fields missing when it's
+    /// deserialized should keep their default
+    type Config: Default + DeserializeOwned + 'static;
+
+    /// Other implementations of either part, these are run alongside the main
+    /// implementations and checked against them
+    const ALTERNATIVES: &'static [Alternative<Self::Input, Self::Config>] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input, config: &Self::Config) -> Answer;
+    fn part_2(input: &Self::Input, config: &Self::Config) -> Answer;
+}
+
+/// Object safe version of [`Solution`], so every day can be stored in the
+/// registry and run without knowing its input type
+///
+/// This is implemented for every [`Solution`], there's no need to implement
+/// it by hand
+pub trait Solver: Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    fn default_config(&self) -> Box<dyn Any>;
+
+    /// The default config with any fields in `overrides` replaced
+    fn config(&self, overrides: &Table) -> Result<Box<dyn Any>, toml::de::Error>;
+
+    /// ## Panics
+    ///
+    /// If `input` or `config` weren't produced by this solver
+    fn solve(&self, part: Part, input: &dyn Any, config: &dyn Any) -> Answer;
+
+    /// Names of every implementation of `part`, starting with [`MAIN`]
+    fn implementations(&self, part: Part) -> Vec<&'static str>;
+
+    /// Solves `part` with the implementation called `name`, `None` if there
+    /// isn't one
+    ///
+    /// ## Panics
+    ///
+    /// If `input` or `config` weren't produced by this solver
+    fn solve_with(
+        &self,
+        part: Part,
+        name: &str,
+        input: &dyn Any,
+        config: &dyn Any,
+    ) -> Option<Answer>;
+
+    /// Parses the input and solves a single part with the default config
+    fn run(&self, part: Part, input: &str) -> Result<Answer, ParseError> {
+        let input = self.parse(input)?;
+        Ok(self.solve(part, input.as_ref(), self.default_config().as_ref()))
+    }
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn default_config(&self) -> Box<dyn Any> {
+        Box::new(S::Config::default())
+    }
+
+    fn config(&self, overrides: &Table) -> Result<Box<dyn Any>, toml::de::Error> {
+        if overrides.is_empty() {
+            return Ok(self.default_config());
+        }
+        if TypeId::of::<S::Config>() == TypeId::of::<()>() {
+            return Err(toml::de::Error::custom("there's nothing to configure"));
+        }
+        let config: S::Config = Value::Table(overrides.clone()).try_into()?;
+        Ok(Box::new(config))
+    }
+
+    fn solve(&self, part: Part, input: &dyn Any, config: &dyn Any) -> Answer {
+        let (input, config) = (downcast(input), downcast(config));
+        match part {
+            Part::One => S::part_1(input, config),
+            Part::Two => S::part_2(input, config),
+        }
+    }
+
+    fn implementations(&self, part: Part) -> Vec<&'static str> {
+        let alternatives = S::ALTERNATIVES.iter().filter(|a| a.part == part);
+        std::iter::once(MAIN)
+            .chain(alternatives.map(|a| a.name))
+            .collect()
+    }
+
+    fn solve_with(
+        &self,
+        part: Part,
+        name: &str,
+        input: &dyn Any,
+        config: &dyn Any,
+    ) -> Option<Answer> {
+        if name == MAIN {
+            return Some(self.solve(part, input, config));
+        }
+        S::ALTERNATIVES
+            .iter()
+            .find(|a| a.part == part && a.name == name)
+            .map(|a| (a.solve)(downcast(input), downcast(config)))
+    }
+}
+
+fn downcast<T: 'static>(value: &dyn Any) -> &T {
+    value
+        .downcast_ref::<T>()
+        .expect("value should have been produced by the same solution")
+}
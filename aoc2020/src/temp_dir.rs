@@ -0,0 +1,62 @@
+//! Scratch directories for tests which need real files
+
+use std::{
+    env, fs,
+    ops::Deref,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// An empty directory under the system's temporary directory, which is
+/// deleted along with everything in it when this is dropped, so it's cleaned
+/// up even if the test fails
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    /// `name` has to be unique among the tests, as they run at the same time
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("aoc-{}-{}", name, process::id()));
+        // Left over from a run which was killed
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("failed to create a temporary directory");
+        Self { path }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_deleted_when_dropped() {
+        let dir = TempDir::new("temp-dir");
+        fs::write(dir.join("file.txt"), "").unwrap();
+        let path = dir.to_path_buf();
+        assert!(path.is_dir());
+        drop(dir);
+        assert!(!path.exists());
+    }
+}